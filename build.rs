@@ -0,0 +1,10 @@
+fn main() {
+    #[cfg(feature = "ffi")]
+    {
+        let crate_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+        cbindgen::generate(&crate_dir)
+            .expect("failed to generate the C header")
+            .write_to_file(std::path::Path::new(&crate_dir).join("include/plain_msgbox.h"));
+        println!("cargo:rerun-if-changed=src/ffi.rs");
+    }
+}
@@ -0,0 +1,148 @@
+//! The derive macro of [`plain-msgbox`](https://github.com/optimalstrategy/plain-msgbox),
+//! see `plain_msgbox::MsgBox`.
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Field, Fields, LitStr};
+
+/// Derive `plain_msgbox::MsgBox` for a struct with named fields, displaying every field
+/// as a key-value entry. The keys are the field names with the underscores replaced by
+/// the spaces and the first letter capitalized, and the values are displayed with `Display`.
+///
+/// The fields accept the `#[msgbox(...)]` attributes:
+/// - `rename = "Key"` displays the field with the given key;
+/// - `skip` omits the field;
+/// - `nested` displays the fields of a nested struct implementing `MsgBox` under the key.
+#[proc_macro_derive(MsgBox, attributes(msgbox))]
+pub fn derive_msgbox(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// The options of a field set by its `#[msgbox(...)]` attributes.
+#[derive(Default)]
+struct FieldOptions {
+    rename: Option<String>,
+    skip: bool,
+    nested: bool,
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                let message = "MsgBox can only be derived for the structs with named fields";
+                return Err(Error::new_spanned(&input.ident, message));
+            }
+        },
+        _ => {
+            let message = "MsgBox can only be derived for structs";
+            return Err(Error::new_spanned(&input.ident, message));
+        }
+    };
+
+    let mut entries = Vec::with_capacity(fields.len());
+    for field in fields {
+        let options = field_options(field)?;
+        if options.skip {
+            continue;
+        }
+        let ident = field.ident.as_ref().expect("the fields are named");
+        let key = format!(
+            "{}:",
+            options
+                .rename
+                .unwrap_or_else(|| humanize(&ident.to_string()))
+        );
+        entries.push(if options.nested {
+            quote! {
+                entries.push(::plain_msgbox::KeyValue::header(#key).with_level(level));
+                entries.extend(::plain_msgbox::MsgBox::entries(&self.#ident, level + 1));
+            }
+        } else {
+            quote! {
+                entries.push(
+                    ::plain_msgbox::KeyValue::new(#key, ::std::string::ToString::to_string(&self.#ident))
+                        .with_level(level),
+                );
+            }
+        });
+    }
+
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::plain_msgbox::MsgBox for #name #type_generics #where_clause {
+            fn entries(&self, level: usize) -> ::std::vec::Vec<::plain_msgbox::KeyValue<'static>> {
+                let mut entries = ::std::vec::Vec::new();
+                #(#entries)*
+                entries
+            }
+        }
+    })
+}
+
+/// Parse the `#[msgbox(...)]` attributes of the field.
+fn field_options(field: &Field) -> syn::Result<FieldOptions> {
+    let mut options = FieldOptions::default();
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("msgbox"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                options.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("skip") {
+                options.skip = true;
+            } else if meta.path.is_ident("nested") {
+                options.nested = true;
+            } else {
+                return Err(meta.error("expected `rename`, `skip`, or `nested`"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(options)
+}
+
+/// Turn the name of a field into a key: `call_stack_size` becomes `Call stack size`.
+fn humanize(name: &str) -> String {
+    let name = name.trim_start_matches("r#").replace('_', " ");
+    let mut chars = name.trim().chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_humanize() {
+        assert_eq!(humanize("call_stack_size"), "Call stack size");
+        assert_eq!(humanize("r#type"), "Type");
+        assert_eq!(humanize("_private"), "Private");
+    }
+
+    #[test]
+    fn test_expand_errors() {
+        let input = syn::parse_quote!(
+            struct Tuple(u8);
+        );
+        assert!(expand(&input).is_err());
+        let input = syn::parse_quote!(
+            struct Options {
+                #[msgbox(flatten)]
+                inner: u8,
+            }
+        );
+        let error = expand(&input).err().unwrap();
+        assert_eq!(error.to_string(), "expected `rename`, `skip`, or `nested`");
+    }
+}
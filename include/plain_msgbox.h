@@ -0,0 +1,52 @@
+#ifndef PLAIN_MSGBOX_H
+#define PLAIN_MSGBOX_H
+
+/* Generated by cbindgen from src/ffi.rs, do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * The options of the generated box.
+ */
+typedef struct PlainMsgboxOptions {
+  /**
+   * The caption attached to the bottom border, or null.
+   */
+  const char *caption;
+  /**
+   * Use the DOS box drawing characters.
+   */
+  bool dos;
+  /**
+   * The fixed width of the box, borders included, or zero to fit the content.
+   */
+  uintptr_t width;
+} PlainMsgboxOptions;
+
+/**
+ * Generate a message box displaying the given UTF-8 lines.
+ * If `options` is null, the box has the default style and no caption.
+ *
+ * Returns null if any of the strings isn't valid UTF-8. The returned string
+ * must be freed with [`plain_msgbox_free`].
+ *
+ * # Safety
+ * `lines` must point to `count` valid null-terminated strings, and `options` must be
+ * either null or a valid pointer whose caption is either null or a valid null-terminated string.
+ */
+char *plain_msgbox_generate(const char *const *lines,
+                            uintptr_t count,
+                            const struct PlainMsgboxOptions *options);
+
+/**
+ * Free a string returned by [`plain_msgbox_generate`]. Does nothing if the string is null.
+ *
+ * # Safety
+ * The string must have been returned by [`plain_msgbox_generate`] and not freed yet.
+ */
+void plain_msgbox_free(char *msgbox);
+
+#endif  /* PLAIN_MSGBOX_H */
@@ -0,0 +1,358 @@
+//! Truncating and wrapping the text with the embedded ANSI escape sequences.
+use std::borrow::Cow;
+
+use crate::{grapheme, WidthMeasurer, RESET};
+
+/// A piece of the text with the embedded escape sequences.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Token<'t> {
+    /// A run of the visible text.
+    Text(&'t str),
+    /// A whole escape sequence.
+    Escape(&'t str),
+}
+
+/// Split the text into the runs of the visible text and the escape sequences,
+/// with their byte offsets.
+pub(crate) fn tokens(text: &str) -> impl Iterator<Item = (usize, Token<'_>)> {
+    let mut start = 0;
+    std::iter::from_fn(move || {
+        let rest = &text[start..];
+        let token_start = start;
+        let token = match find_escape(rest) {
+            _ if rest.is_empty() => return None,
+            Some(0) => Token::Escape(&rest[..escape_len(rest)]),
+            Some(end) => Token::Text(&rest[..end]),
+            None => Token::Text(rest),
+        };
+        start += match token {
+            Token::Text(run) | Token::Escape(run) => run.len(),
+        };
+        Some((token_start, token))
+    })
+}
+
+/// The byte offset of the first escape character of the text. With the `memchr` feature,
+/// the long lines are scanned with the vector instructions of the target.
+pub(crate) fn find_escape(text: &str) -> Option<usize> {
+    #[cfg(feature = "memchr")]
+    return memchr::memchr(b'\x1b', text.as_bytes());
+    #[cfg(not(feature = "memchr"))]
+    return text.find('\x1b');
+}
+
+/// Split the text into the runs between the escape sequences, with their byte offsets.
+pub(crate) fn visible_runs(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    tokens(text).filter_map(|(start, token)| match token {
+        Token::Text(run) => Some((start, run)),
+        Token::Escape(_) => None,
+    })
+}
+
+/// Closes the hyperlink opened by [`hyperlink`].
+const LINK_END: &str = "\x1b]8;;\x1b\\";
+
+/// The length of the escape sequence at the start of the text: a control sequence
+/// like `ESC [ 1 m`, an operating system command like the OSC 8 hyperlinks terminated
+/// by `BEL` or `ESC \`, or an escape followed by a single character.
+pub(crate) fn escape_len(text: &str) -> usize {
+    let mut chars = text.char_indices().skip(1);
+    match chars.next() {
+        Some((_, '[')) => chars
+            .find(|(_, c)| ('\x40'..='\x7e').contains(c))
+            .map_or(text.len(), |(end, c)| end + c.len_utf8()),
+        Some((_, ']')) => match text[2..].find(['\x07', '\x1b']) {
+            Some(end) if text[2 + end..].starts_with("\x1b\\") => 2 + end + 2,
+            Some(end) if text[2 + end..].starts_with('\x07') => 2 + end + 1,
+            Some(end) => 2 + end,
+            None => text.len(),
+        },
+        Some((index, c)) => index + c.len_utf8(),
+        None => text.len(),
+    }
+}
+
+/// Whether the escape sequence changes the style of the text that follows it.
+pub(crate) fn is_style(escape: &str) -> bool {
+    escape.starts_with("\x1b[") && escape.ends_with('m')
+}
+
+/// Whether the escape sequence resets all of the styles.
+fn is_reset(escape: &str) -> bool {
+    escape == "\x1b[0m" || escape == "\x1b[m"
+}
+
+/// Whether the escape sequence starts a hyperlink, `Some(false)` if it ends one,
+/// or `None` if it isn't an OSC 8 sequence.
+fn is_link_start(escape: &str) -> Option<bool> {
+    let params = escape.strip_prefix("\x1b]8;")?;
+    let target = params.split_once(';')?.1;
+    let target = target
+        .trim_end_matches(['\x07', '\\'])
+        .trim_end_matches('\x1b');
+    Some(!target.is_empty())
+}
+
+/// Make the text a terminal hyperlink to the given target with the OSC 8 escape sequences,
+/// like a clickable file path. The terminals that don't support the hyperlinks only display the text.
+/// The sequences take no columns if the text is measured with [`AnsiWidth`](crate::AnsiWidth).
+///
+/// ```
+/// # use plain_msgbox::*;
+/// let link = hyperlink("report.html", "file:///tmp/report.html");
+/// assert_eq!(link, "\x1b]8;;file:///tmp/report.html\x1b\\report.html\x1b]8;;\x1b\\");
+///
+/// let config = TextBoxConfig::ascii().with_measurer(&AnsiWidth(CharWidth));
+/// let msg = generate_with_config(&[format!("See {}", link)], config);
+/// assert_eq!(msg.lines().next(), Some("+-----------------+"));
+/// ```
+pub fn hyperlink(text: &str, target: &str) -> String {
+    format!("\x1b]8;;{}\x1b\\{}{}", target, text, LINK_END)
+}
+
+/// Truncate the text with the embedded escape sequences to at most `max_width` columns,
+/// measuring the visible text with the given measurer. The escape sequences take no columns
+/// and are never split, and the styles and the [hyperlink] that are active at the cut are closed,
+/// so they don't bleed into the border of the box.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// assert_eq!(truncate_styled("\x1b[31mError: disk full\x1b[0m", 5, &CharWidth), "\x1b[31mError\x1b[0m");
+/// assert_eq!(truncate_styled("\x1b[1mok\x1b[0m", 5, &CharWidth), "\x1b[1mok\x1b[0m");
+/// ```
+pub fn truncate_styled<'t, M: WidthMeasurer + ?Sized>(
+    text: &'t str,
+    max_width: usize,
+    measurer: &M,
+) -> Cow<'t, str> {
+    let mut remaining = max_width;
+    let mut active = false;
+    let mut linked = false;
+    for (start, token) in tokens(text) {
+        match token {
+            Token::Escape(escape) if is_style(escape) => active = !is_reset(escape),
+            Token::Escape(escape) => linked = is_link_start(escape).unwrap_or(linked),
+            Token::Text(run) => {
+                let width = measurer.width(run);
+                if width <= remaining {
+                    remaining -= width;
+                    continue;
+                }
+                let end = start + measurer.truncate(run, remaining).len();
+                if !active && !linked {
+                    return Cow::Borrowed(&text[..end]);
+                }
+                let mut truncated = text[..end].to_string();
+                if linked {
+                    truncated.push_str(LINK_END);
+                }
+                if active {
+                    truncated.push_str(RESET);
+                }
+                return Cow::Owned(truncated);
+            }
+        }
+    }
+    Cow::Borrowed(text)
+}
+
+/// Break the text with the embedded escape sequences into lines that are at most `width`
+/// columns wide, measuring the visible text with the given measurer like [`truncate_styled`].
+/// The styles and the [hyperlink] that are active at a break are closed at the end of the line
+/// and reopened at the start of the next one. Every line has at least one character.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// assert_eq!(
+///     wrap_styled("ok \x1b[32mpassed\x1b[0m", 5, &CharWidth),
+///     ["ok \x1b[32mpa\x1b[0m", "\x1b[32mssed\x1b[0m"]
+/// );
+/// ```
+pub fn wrap_styled<M: WidthMeasurer + ?Sized>(
+    text: &str,
+    width: usize,
+    measurer: &M,
+) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    let mut remaining = width;
+    let mut active = Vec::new();
+    let mut link = None;
+    for (_, token) in tokens(text) {
+        let mut run = match token {
+            Token::Escape(escape) => {
+                line.push_str(escape);
+                if is_reset(escape) {
+                    active.clear();
+                } else if is_style(escape) {
+                    active.push(escape);
+                } else if let Some(start) = is_link_start(escape) {
+                    link = if start { Some(escape) } else { None };
+                }
+                continue;
+            }
+            Token::Text(run) => run,
+        };
+
+        loop {
+            let run_width = measurer.width(run);
+            if run_width <= remaining {
+                line.push_str(run);
+                remaining -= run_width;
+                break;
+            }
+
+            let mut fit = measurer.truncate(run, remaining);
+            if fit.is_empty() && remaining == width {
+                fit = &run[..grapheme::first_len(run)];
+            }
+            line.push_str(fit);
+            run = &run[fit.len()..];
+            if run.is_empty() {
+                remaining = 0;
+                break;
+            }
+
+            if link.is_some() {
+                line.push_str(LINK_END);
+            }
+            if !active.is_empty() {
+                line.push_str(RESET);
+            }
+            lines.push(std::mem::take(&mut line));
+            active
+                .iter()
+                .chain(&link)
+                .for_each(|escape| line.push_str(escape));
+            remaining = width;
+        }
+    }
+    lines.push(line);
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AnsiWidth, ByteWidth, CharWidth};
+
+    #[test]
+    fn test_tokens() {
+        let tokens = tokens("a\x1b[1mb\x1b[0m\x1b").collect::<Vec<_>>();
+        assert_eq!(
+            tokens,
+            [
+                (0, Token::Text("a")),
+                (1, Token::Escape("\x1b[1m")),
+                (5, Token::Text("b")),
+                (6, Token::Escape("\x1b[0m")),
+                (10, Token::Escape("\x1b")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_truncate_styled() {
+        let text = "\x1b[31mab\x1b[0mcd";
+        assert_eq!(truncate_styled(text, 1, &ByteWidth), "\x1b[31ma\x1b[0m");
+        assert_eq!(truncate_styled(text, 2, &ByteWidth), "\x1b[31mab\x1b[0m");
+        assert_eq!(truncate_styled(text, 3, &ByteWidth), "\x1b[31mab\x1b[0mc");
+        assert_eq!(truncate_styled(text, 0, &ByteWidth), "\x1b[31m\x1b[0m");
+    }
+
+    #[test]
+    fn test_wrap_styled() {
+        assert_eq!(wrap_styled("", 3, &CharWidth), [""]);
+        assert_eq!(
+            wrap_styled("abcde", 0, &CharWidth),
+            ["a", "b", "c", "d", "e"]
+        );
+        assert_eq!(
+            wrap_styled("\x1b[1m\x1b[31mabc\x1b[0md", 2, &CharWidth),
+            ["\x1b[1m\x1b[31mab\x1b[0m", "\x1b[1m\x1b[31mc\x1b[0md"]
+        );
+    }
+
+    #[test]
+    fn test_fixed_width_closes_styles() {
+        let config = crate::TextBoxConfig::ascii().with_measurer(&crate::AnsiWidth(ByteWidth));
+        let renderer = crate::Renderer::with_width(config, 7);
+        assert_eq!(
+            renderer.render(&[String::from("\x1b[31mError\x1b[0m")]),
+            "+-----+\n| \x1b[31mErr\x1b[0m |\n+-----+"
+        );
+    }
+
+    #[test]
+    fn test_hyperlinks() {
+        let link = hyperlink("abcd", "https://example.com");
+        assert_eq!(crate::AnsiWidth(ByteWidth).width(&link), 4);
+        assert_eq!(
+            tokens("\x1b]8;;x\x07a").collect::<Vec<_>>(),
+            [(0, Token::Escape("\x1b]8;;x\x07")), (7, Token::Text("a"))]
+        );
+
+        let start = "\x1b]8;;https://example.com\x1b\\";
+        assert_eq!(
+            truncate_styled(&link, 2, &ByteWidth),
+            format!("{}ab{}", start, LINK_END)
+        );
+        assert_eq!(
+            wrap_styled(&link, 3, &ByteWidth),
+            [
+                format!("{}abc{}", start, LINK_END),
+                format!("{}d{}", start, LINK_END)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_long_lines() {
+        let mut line = "a".repeat(10_000);
+        assert_eq!(find_escape(&line), None);
+        line.push_str("\x1b[1mb\x1b[0m");
+        assert_eq!(find_escape(&line), Some(10_000));
+        assert_eq!(AnsiWidth(CharWidth).width(&line), 10_001);
+        assert_eq!(CharWidth.truncate(&line, 9_999).len(), 9_999);
+        assert_eq!(CharWidth.truncate("aé", 1), "a");
+    }
+
+    #[test]
+    fn test_ansi_aware() {
+        let link = hyperlink("docs", "https://example.com");
+        let lines = [format!("\x1b[1;32mok\x1b[0m {}", link), "plain".to_string()];
+        let config = crate::TextBoxConfig::ascii().ansi_aware();
+
+        let msg = crate::generate_with_config(&lines, config);
+        let rows = msg.lines().collect::<Vec<_>>();
+        assert_eq!(rows[0], "+---------+");
+        assert_eq!(rows[1], format!("| \x1b[1;32mok\x1b[0m {} |", link));
+        assert_eq!(rows[2], "| plain   |");
+
+        let truncated = crate::Renderer::with_width(config, 6).render(&lines[..1]);
+        assert_eq!(truncated.lines().nth(1), Some("| \x1b[1;32mok\x1b[0m |"));
+    }
+
+    #[test]
+    fn test_markup_escapes() {
+        let link = hyperlink("docs", "http://x");
+        let lines = [
+            format!("**see** {} now", link),
+            "\x1b[31mred text here\x1b[0m".to_string(),
+        ];
+        let config = crate::TextBoxConfig::ascii()
+            .ansi_aware()
+            .with_max_width(10)
+            .with_markup();
+
+        let msg = crate::generate_with_config(&lines, config);
+        let rows = msg.lines().collect::<Vec<_>>();
+        assert_eq!(rows[0], "+------+");
+        assert_eq!(rows[1], "| \x1b[1msee\x1b[0m  |");
+        assert_eq!(rows[2], format!("| {} |", link));
+        assert_eq!(rows[3], "| now  |");
+        assert_eq!(rows[4], "| \x1b[31mred\x1b[0m  |");
+        assert_eq!(rows[5], "| \x1b[31mtext\x1b[0m |");
+        assert_eq!(rows[6], "| \x1b[31mhere\x1b[0m |");
+    }
+}
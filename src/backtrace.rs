@@ -0,0 +1,207 @@
+//! Framing the backtraces captured by `std::backtrace`.
+use std::backtrace::Backtrace;
+
+use crate::{generate_with_config, TextBoxConfig};
+
+/// The frame of the standard library that the frames of the runtime are called by.
+const BEGIN_SHORT_BACKTRACE: &str = "__rust_begin_short_backtrace";
+/// The frame of the standard library that the frames of the panic machinery are called from.
+const END_SHORT_BACKTRACE: &str = "__rust_end_short_backtrace";
+/// The prefixes of the symbols of the standard library.
+const INTERNAL_PREFIXES: &[&str] = &[
+    "std::", "core::", "alloc::", "<std::", "<core::", "<alloc::",
+];
+
+/// A frame parsed from the text of a backtrace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Frame<'t> {
+    index: &'t str,
+    symbol: &'t str,
+    location: Option<&'t str>,
+}
+
+/// Generate a message box displaying the frames of the backtrace, with the frame indexes
+/// and the symbols aligned into columns and the source locations under the symbols.
+/// With [`trim_internal_frames`](TextBoxConfig::trim_internal_frames), the frames of
+/// the runtime, of the panic machinery and of the standard library are skipped.
+/// The box is captioned with `"backtrace"` unless the config has a caption.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// use std::backtrace::Backtrace;
+///
+/// let config = TextBoxConfig::default().with_caption("crash");
+/// assert_eq!(box_backtrace(&Backtrace::disabled(), config), "\
+/// ╭────────────────────╮
+/// │ disabled backtrace │
+/// <crash>──────────────╯");
+///
+/// let msg = box_backtrace(&Backtrace::force_capture(), config.trim_internal_frames());
+/// assert!(!msg.contains("std::rt::lang_start"));
+/// ```
+pub fn box_backtrace(backtrace: &Backtrace, config: TextBoxConfig<'_>) -> String {
+    let config = TextBoxConfig {
+        last_line_caption: config.last_line_caption.or(Some("backtrace")),
+        ..config
+    };
+    let text = backtrace.to_string();
+    let mut frames = parse_frames(&text);
+    if frames.is_empty() {
+        let lines = text
+            .trim_end()
+            .lines()
+            .map(String::from)
+            .collect::<Vec<_>>();
+        return generate_with_config(&lines, config);
+    }
+
+    if config.trim_internal_frames {
+        frames = trim_frames(frames);
+    }
+    generate_with_config(&layout(&frames), config)
+}
+
+/// Parse the frames of the backtrace formatted by `Backtrace`'s `Display`.
+/// Only the first source location of every frame is kept.
+fn parse_frames(text: &str) -> Vec<Frame<'_>> {
+    let mut frames: Vec<Frame<'_>> = Vec::new();
+    for line in text.lines().map(str::trim) {
+        if let Some(location) = line.strip_prefix("at ") {
+            match frames.last_mut() {
+                Some(frame) if frame.location.is_none() => frame.location = Some(location),
+                _ => {}
+            }
+            continue;
+        }
+
+        let frame = line
+            .split_once(": ")
+            .filter(|(index, _)| !index.is_empty() && index.bytes().all(|b| b.is_ascii_digit()));
+        if let Some((index, symbol)) = frame {
+            frames.push(Frame {
+                index,
+                symbol,
+                location: None,
+            });
+        }
+    }
+    frames
+}
+
+/// Keep the frames between the panic machinery and the runtime, skipping the ones
+/// of the standard library.
+fn trim_frames(frames: Vec<Frame<'_>>) -> Vec<Frame<'_>> {
+    let is_marker = |frame: &Frame<'_>, marker| frame.symbol.ends_with(marker);
+    let start = frames
+        .iter()
+        .rposition(|frame| is_marker(frame, END_SHORT_BACKTRACE))
+        .map_or(0, |index| index + 1);
+    let end = frames
+        .iter()
+        .position(|frame| is_marker(frame, BEGIN_SHORT_BACKTRACE))
+        .unwrap_or(frames.len())
+        .max(start);
+
+    frames[start..end]
+        .iter()
+        .filter(|frame| {
+            !INTERNAL_PREFIXES
+                .iter()
+                .any(|prefix| frame.symbol.starts_with(prefix))
+        })
+        .copied()
+        .collect()
+}
+
+/// Lay out the frames as lines: the indexes are right-aligned, and the symbols
+/// and the source locations start at the same column.
+fn layout(frames: &[Frame<'_>]) -> Vec<String> {
+    let index_width = frames
+        .iter()
+        .map(|frame| frame.index.len())
+        .max()
+        .unwrap_or(0);
+
+    let mut lines = Vec::with_capacity(frames.len() * 2);
+    for frame in frames {
+        lines.push(format!(
+            "{:>width$}: {}",
+            frame.index,
+            frame.symbol,
+            width = index_width
+        ));
+        if let Some(location) = frame.location {
+            lines.push(format!(
+                "{:width$}  at {}",
+                "",
+                location,
+                width = index_width
+            ));
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "\
+   0: std::backtrace::Backtrace::force_capture
+             at /rustc/library/std/src/backtrace.rs:312:13
+   1: app::parse
+             at ./src/parse.rs:10:5
+             at ./src/inlined.rs:1:1
+   2: app::main
+             at ./src/main.rs:4:5
+   3: std::sys::backtrace::__rust_begin_short_backtrace
+             at /rustc/library/std/src/sys/backtrace.rs:166:18
+   4: std::rt::lang_start
+  10: main
+  11: _start
+";
+
+    #[test]
+    fn test_parse_frames() {
+        let frames = parse_frames(TEXT);
+        assert_eq!(frames.len(), 7);
+        assert_eq!(
+            frames[1],
+            Frame {
+                index: "1",
+                symbol: "app::parse",
+                location: Some("./src/parse.rs:10:5"),
+            }
+        );
+        assert_eq!(frames[6].location, None);
+        assert!(parse_frames("disabled backtrace").is_empty());
+    }
+
+    #[test]
+    fn test_layout() {
+        let trimmed = trim_frames(parse_frames(TEXT));
+        assert_eq!(
+            layout(&trimmed),
+            [
+                "1: app::parse",
+                "   at ./src/parse.rs:10:5",
+                "2: app::main",
+                "   at ./src/main.rs:4:5",
+            ]
+        );
+
+        let frames = parse_frames(TEXT);
+        let lines = layout(&frames[5..]);
+        assert_eq!(lines, ["10: main", "11: _start"]);
+        assert_eq!(layout(&frames[4..5]), ["4: std::rt::lang_start"]);
+
+        let panic = "\
+   0: std::panicking::begin_panic
+   1: std::panicking::begin_panic::{{closure}}
+   2: std::sys::backtrace::__rust_end_short_backtrace
+   3: app::main
+";
+        let trimmed = trim_frames(parse_frames(panic));
+        assert_eq!(layout(&trimmed), ["3: app::main"]);
+    }
+}
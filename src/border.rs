@@ -0,0 +1,179 @@
+//! The pluggable borders drawn around the content of the boxes.
+use std::borrow::Cow;
+
+use crate::{spacer::push_bars, TextBoxConfig};
+
+/// A row of the box border, see [`Border::segments`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BorderRow {
+    /// The top border.
+    Top,
+    /// A row with the content between the vertical bars.
+    Content,
+    /// A horizontal rule between the content rows.
+    Separator,
+    /// The bottom border, the caption takes the place of its left or right piece.
+    Bottom,
+}
+
+/// The pieces of a border row: the left piece, the horizontal fill, and the right piece.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BorderSegments<'s> {
+    pub left: Cow<'s, str>,
+    /// The horizontal part of the row, ignored for the content rows.
+    pub fill: Cow<'s, str>,
+    pub right: Cow<'s, str>,
+}
+
+/// The box border drawn around the content instead of the box drawing characters of the config.
+///
+/// The [`TextBoxConfig`] itself is the border made of its characters, so custom borders
+/// can fall back to a config for the rows they don't change.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// /// A border with a repeating pattern at the top and the bottom.
+/// struct Dashes(TextBoxConfig<'static>);
+///
+/// impl Border for Dashes {
+///     fn segments(&self, row: BorderRow, width: usize) -> BorderSegments<'_> {
+///         match row {
+///             BorderRow::Top | BorderRow::Bottom => BorderSegments {
+///                 left: "*".into(),
+///                 fill: "=-".chars().cycle().take(width).collect::<String>().into(),
+///                 right: "*".into(),
+///             },
+///             row => self.0.segments(row, width),
+///         }
+///     }
+/// }
+///
+/// let border = Dashes(TextBoxConfig::ascii());
+/// let msg = generate_with_config(&[String::from("Ahoy")], TextBoxConfig::default().with_border(&border));
+/// assert_eq!(msg, "\
+/// *=-=-=-*
+/// | Ahoy |
+/// *=-=-=-*");
+/// ```
+pub trait Border {
+    /// Get the pieces of the given border row, with the fill `width` columns wide.
+    fn segments(&self, row: BorderRow, width: usize) -> BorderSegments<'_>;
+}
+
+impl Border for TextBoxConfig<'_> {
+    fn segments(&self, row: BorderRow, width: usize) -> BorderSegments<'_> {
+        let (left, right) = glyphs(self, row);
+        let mut fill = String::new();
+        if row != BorderRow::Content {
+            push_bars(&mut fill, self.horizontal_bar, width);
+        }
+        BorderSegments {
+            left: left.into(),
+            fill: fill.into(),
+            right: right.into(),
+        }
+    }
+}
+
+/// The left and the right pieces of the given border row drawn with the characters of the config.
+pub(crate) fn glyphs<'c>(config: &TextBoxConfig<'c>, row: BorderRow) -> (&'c str, &'c str) {
+    match row {
+        BorderRow::Top => (config.left_top_corner, config.right_top_corner),
+        BorderRow::Content => (config.vertical_bar, config.vertical_bar),
+        BorderRow::Separator => (config.left_tee, config.right_tee),
+        BorderRow::Bottom => (config.left_bottom_corner, config.right_bottom_corner),
+    }
+}
+
+/// Append the pieces of the given border row, with the fill `width` columns wide,
+/// skipping the left or the right piece if it isn't `shown`. The right piece of
+/// a [ragged](TextBoxConfig::ragged) box is always skipped.
+pub(crate) fn push_border(
+    out: &mut String,
+    config: &TextBoxConfig<'_>,
+    row: BorderRow,
+    width: usize,
+    (show_left, show_right): (bool, bool),
+) {
+    let show_right = show_right && !config.ragged;
+    match config.border {
+        Some(border) => {
+            let segments = border.segments(row, width);
+            if show_left {
+                out.push_str(&segments.left);
+            }
+            out.push_str(&segments.fill);
+            if show_right {
+                out.push_str(&segments.right);
+            }
+        }
+        None => {
+            let (left, right) = glyphs(config, row);
+            if show_left {
+                out.push_str(left);
+            }
+            push_bars(out, config.horizontal_bar, width);
+            if show_right {
+                out.push_str(right);
+            }
+        }
+    }
+}
+
+/// Append the pieces of the given border row like [`push_border`], embedding the marker into
+/// the fill one column away from the right piece, or from the left one if the box is
+/// [mirrored](TextBoxConfig::mirrored). The marker is skipped if it doesn't fit into the fill.
+pub(crate) fn push_marked_border(
+    out: &mut String,
+    config: &TextBoxConfig<'_>,
+    row: BorderRow,
+    width: usize,
+    (show_left, show_right): (bool, bool),
+    marker: Option<&str>,
+) {
+    let marker_width = marker.map_or(0, |marker| marker.chars().count());
+    let marker = match marker.filter(|_| marker_width < width) {
+        Some(marker) => marker,
+        None => return push_border(out, config, row, width, (show_left, show_right)),
+    };
+
+    let rest = width - marker_width - 1;
+    let (before, after) = if config.mirrored {
+        (1, rest)
+    } else {
+        (rest, 1)
+    };
+    push_border(out, config, row, before, (show_left, false));
+    out.push_str(marker);
+    push_border(out, config, row, after, (false, show_right));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_segments() {
+        let config = TextBoxConfig::default();
+        let top = config.segments(BorderRow::Top, 3);
+        assert_eq!((&*top.left, &*top.fill, &*top.right), ("╭", "───", "╮"));
+
+        let content = config.segments(BorderRow::Content, 3);
+        assert_eq!(
+            (&*content.left, &*content.fill, &*content.right),
+            ("│", "", "│")
+        );
+    }
+
+    #[test]
+    fn test_custom_border_with_caption() {
+        let ascii = TextBoxConfig::ascii();
+        let config = TextBoxConfig::default()
+            .with_caption("C")
+            .with_border(&ascii);
+        let lines = [String::from("a")];
+        let msg = crate::generate_separated(&[&lines, &lines], config);
+
+        assert_eq!(msg, "+---+\n| a |\n+---+\n| a |\n<C>-+");
+    }
+}
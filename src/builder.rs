@@ -0,0 +1,208 @@
+//! Message boxes assembled one line at a time.
+use std::{fmt, mem};
+
+use crate::{kv, KeyValue, Renderer, TextBoxConfig};
+
+/// A message box assembled line by line, for the code paths that discover the content
+/// gradually. The consecutive key-value entries have their values aligned into a column
+/// like the ones of [`generate_key_values`](crate::generate_key_values), and the sections
+/// are separated by the divider rows joined with the side borders by the tees.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// let msg = BoxBuilder::new()
+///     .line("Build finished")
+///     .blank()
+///     .kv("target", "x86_64")
+///     .kv("profile", "release")
+///     .section("Optimizations")
+///     .kv("opt-level", 3)
+///     .kv("lto", true)
+///     .caption("Cfg")
+///     .build();
+///
+/// assert_eq!(msg, "\
+/// ╭──────────────────╮
+/// │ Build finished   │
+/// │                  │
+/// │ target:  x86_64  │
+/// │ profile: release │
+/// ├─ Optimizations ──┤
+/// │ opt-level: 3     │
+/// │ lto:       true  │
+/// <Cfg>──────────────╯");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct BoxBuilder<'a> {
+    config: TextBoxConfig<'a>,
+    items: Vec<Item>,
+}
+
+/// A line added to a [`BoxBuilder`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Item {
+    Line(String),
+    KeyValue(String, String),
+    /// The divider starting a section, with its title if any.
+    Divider(Option<String>),
+}
+
+impl<'a> BoxBuilder<'a> {
+    /// Create an empty box with the default config.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an empty box rendered with the given config.
+    pub fn with_config(config: TextBoxConfig<'a>) -> Self {
+        Self {
+            config,
+            items: Vec::new(),
+        }
+    }
+
+    /// Add a line to the end of the box.
+    pub fn line(mut self, line: impl Into<String>) -> Self {
+        self.items.push(Item::Line(line.into()));
+        self
+    }
+
+    /// Add an empty line to the end of the box.
+    pub fn blank(self) -> Self {
+        self.line(String::new())
+    }
+
+    /// Add a `key: value` line to the end of the box.
+    pub fn kv(mut self, key: impl fmt::Display, value: impl fmt::Display) -> Self {
+        let item = Item::KeyValue(format!("{}:", key), value.to_string());
+        self.items.push(item);
+        self
+    }
+
+    /// Start a new section with a divider row displaying the given title.
+    pub fn section(mut self, title: impl Into<String>) -> Self {
+        self.items.push(Item::Divider(Some(title.into())));
+        self
+    }
+
+    /// Start a new section with a divider row without a title.
+    pub fn divider(mut self) -> Self {
+        self.items.push(Item::Divider(None));
+        self
+    }
+
+    /// Caption the box, replacing the caption of the config.
+    pub fn caption(self, caption: &'a str) -> Self {
+        Self {
+            config: self.config.with_caption(caption),
+            ..self
+        }
+    }
+
+    /// The number of lines and dividers added so far.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Whether no lines were added yet.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Render the box.
+    pub fn build(&self) -> String {
+        let renderer = Renderer::new(self.config);
+        let measure = renderer.config().measure();
+        let max_width = renderer.content_limit();
+
+        let mut groups = vec![(None, Vec::new())];
+        let mut entries = Vec::new();
+        // Lay out the run of the key-value entries before the item, if any.
+        let flush = |groups: &mut Vec<(_, Vec<String>)>, entries: &mut Vec<KeyValue<'_>>| {
+            let (_, lines) = groups.last_mut().expect("there's always a group");
+            lines.extend(kv::layout(&mem::take(entries), measure, max_width));
+        };
+        for item in &self.items {
+            match item {
+                Item::KeyValue(key, value) => entries.push(KeyValue::new(key, value)),
+                Item::Line(line) => {
+                    flush(&mut groups, &mut entries);
+                    let (_, lines) = groups.last_mut().expect("there's always a group");
+                    lines.push(line.clone());
+                }
+                Item::Divider(title) => {
+                    flush(&mut groups, &mut entries);
+                    groups.push((title.as_deref(), Vec::new()));
+                }
+            }
+        }
+        flush(&mut groups, &mut entries);
+
+        match &*groups {
+            [(None, lines)] => renderer.render(lines),
+            groups => renderer.render_divided(groups),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build() {
+        let builder = BoxBuilder::with_config(TextBoxConfig::ascii());
+        assert!(builder.is_empty());
+        assert_eq!(builder.build(), "+--+\n+--+");
+
+        let builder = builder.kv("a", 1).line("--").kv("long", 2).kv("b", true);
+        assert_eq!(builder.len(), 4);
+        assert_eq!(
+            builder.build(),
+            "\
++------------+
+| a: 1       |
+| --         |
+| long: 2    |
+| b:    true |
++------------+"
+        );
+    }
+
+    #[test]
+    fn test_fixed_width() {
+        let config = TextBoxConfig::ascii().with_width(18);
+        let msg = BoxBuilder::with_config(config)
+            .kv("cmd", "cargo build --release")
+            .build();
+        assert_eq!(
+            msg,
+            "\
++----------------+
+| cmd: cargo     |
+|      build     |
+|      --release |
++----------------+"
+        );
+    }
+
+    #[test]
+    fn test_sections() {
+        let builder = BoxBuilder::with_config(TextBoxConfig::ascii())
+            .kv("a", 1)
+            .divider()
+            .line("b")
+            .section("title");
+        assert_eq!(builder.len(), 4);
+        assert_eq!(
+            builder.build(),
+            "\
++---------+
+| a: 1    |
++---------+
+| b       |
++- title -+
++---------+"
+        );
+    }
+}
@@ -0,0 +1,154 @@
+//! The character sets the glyphs of the boxes are drawn with, chosen for the target terminal.
+use crate::{to_ascii_fallback, TextBoxConfig};
+
+/// The characters 0x80 to 0xFF of the code page 437 of the IBM PC, in order.
+const CP437_UPPER: &str = "ÇüéâäàåçêëèïîìÄÅÉæÆôöòûùÿÖÜ¢£¥₧ƒáíóúñÑªº¿⌐¬½¼¡«»░▒▓│┤╡╢╖╕╣║╗╝╜╛┐└┴┬├─┼╞╟╚╔╩╦╠═╬╧╨╤╥╙╘╒╓╫╪┘┌█▄▌▐▀αßΓπΣσµτΦΘΩδ∞φε∩≡±≥≤⌠⌡÷≈°∙·√ⁿ²■\u{a0}";
+
+/// The set of characters the glyphs of a box are drawn with, see [`TextBoxConfig::with_charset`].
+///
+/// The charset is applied when the box is rendered, replacing every glyph of the config
+/// that it can't display: the bars, the corners, the tees, the speech bubble tail, the column
+/// divider, the row decorators, the padding fill and the ellipsis. The lines and the captions are kept as is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Charset {
+    /// Any characters, the glyphs are used as is.
+    Unicode,
+    /// Only the ASCII characters, see [`to_ascii_fallback`].
+    Ascii,
+    /// The characters of the code page 437 of the legacy PC consoles, which has the square
+    /// and the double box drawing characters but not the rounded corners. The rest of the
+    /// glyphs fall back to ASCII.
+    Cp437,
+}
+
+impl Charset {
+    /// Whether the given text can be displayed with this charset.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// assert!(Charset::Cp437.contains("╔═╗"));
+    /// assert!(!Charset::Cp437.contains("╭─╮"));
+    /// assert!(!Charset::Ascii.contains("═"));
+    /// ```
+    pub fn contains(self, text: &str) -> bool {
+        match self {
+            Charset::Unicode => true,
+            Charset::Ascii => text.is_ascii(),
+            Charset::Cp437 => text
+                .chars()
+                .all(|c| c.is_ascii() || CP437_UPPER.contains(c)),
+        }
+    }
+
+    /// Replace the glyphs of the config that this charset can't display.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let config = Charset::Cp437.apply(TextBoxConfig::default());
+    ///
+    /// assert_eq!(generate_with_config(&[String::from("BBS")], config), "\
+    /// ┌─────┐
+    /// │ BBS │
+    /// └─────┘");
+    /// ```
+    pub fn apply(self, config: TextBoxConfig<'_>) -> TextBoxConfig<'_> {
+        match self {
+            Charset::Unicode => config,
+            Charset::Ascii => to_ascii_fallback(&config),
+            Charset::Cp437 => {
+                let config = TextBoxConfig {
+                    left_top_corner: square_corner(config.left_top_corner),
+                    left_bottom_corner: square_corner(config.left_bottom_corner),
+                    right_top_corner: square_corner(config.right_top_corner),
+                    right_bottom_corner: square_corner(config.right_bottom_corner),
+                    ..config
+                };
+                let fallback = to_ascii_fallback(&config);
+                let glyph = |glyph, fallback| {
+                    if self.contains(glyph) {
+                        glyph
+                    } else {
+                        fallback
+                    }
+                };
+
+                TextBoxConfig {
+                    horizontal_bar: glyph(config.horizontal_bar, fallback.horizontal_bar),
+                    vertical_bar: glyph(config.vertical_bar, fallback.vertical_bar),
+                    left_top_corner: glyph(config.left_top_corner, fallback.left_top_corner),
+                    left_bottom_corner: glyph(
+                        config.left_bottom_corner,
+                        fallback.left_bottom_corner,
+                    ),
+                    right_top_corner: glyph(config.right_top_corner, fallback.right_top_corner),
+                    right_bottom_corner: glyph(
+                        config.right_bottom_corner,
+                        fallback.right_bottom_corner,
+                    ),
+                    left_tee: glyph(config.left_tee, fallback.left_tee),
+                    right_tee: glyph(config.right_tee, fallback.right_tee),
+                    tail: glyph(config.tail, fallback.tail),
+                    column_divider: glyph(config.column_divider, fallback.column_divider),
+                    row_prefix: glyph(config.row_prefix, fallback.row_prefix),
+                    row_suffix: glyph(config.row_suffix, fallback.row_suffix),
+                    padding_fill: config.padding_fill.filter(|fill| self.contains(fill)),
+                    ellipsis: config
+                        .ellipsis
+                        .map(|e| glyph(e, fallback.ellipsis.unwrap_or(e))),
+                    border: fallback.border,
+                    ..config
+                }
+            }
+        }
+    }
+}
+
+/// The given glyph, or its ASCII fallback if the charset of the config can't display it
+/// or the box is drawn with the ASCII characters.
+pub(crate) fn glyph<'g>(config: &TextBoxConfig<'_>, [glyph, ascii]: [&'g str; 2]) -> &'g str {
+    if config.charset.contains(glyph) && !config.horizontal_bar.is_ascii() {
+        glyph
+    } else {
+        ascii
+    }
+}
+
+/// Like [`glyph`], for the glyphs displayed within the content rows, which also fall back
+/// to ASCII unless the measurer of the config counts every character as one column.
+pub(crate) fn content_glyph<'g>(config: &TextBoxConfig<'_>, glyphs: [&'g str; 2]) -> &'g str {
+    match glyph(config, glyphs) {
+        glyph if config.measure().width(glyph) == glyph.chars().count() => glyph,
+        _ => glyphs[1],
+    }
+}
+
+/// The square box drawing corner in place of the rounded one.
+fn square_corner(corner: &str) -> &str {
+    match corner {
+        "╭" => "┌",
+        "╮" => "┐",
+        "╰" => "└",
+        "╯" => "┘",
+        corner => corner,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_charsets() {
+        let dos = TextBoxConfig::dos();
+        let cp437 = Charset::Cp437.apply(dos.with_padding_fill("·").with_row_decorators("", "✦"));
+        assert_eq!(cp437.vertical_bar, dos.vertical_bar);
+        assert_eq!(cp437.left_top_corner, dos.left_top_corner);
+        assert_eq!(cp437.padding_fill, Some("·"));
+        assert_eq!(cp437.row_suffix, "");
+
+        let default = Charset::Cp437.apply(TextBoxConfig::default());
+        assert_eq!(default.tail, "`->");
+        assert!(Charset::Ascii.apply(dos).vertical_bar.is_ascii());
+        assert_eq!(Charset::Unicode.apply(dos).vertical_bar, dos.vertical_bar);
+    }
+}
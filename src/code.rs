@@ -0,0 +1,115 @@
+//! Framing the code snippets highlighted by `syntect`.
+use std::sync::OnceLock;
+
+use syntect::{
+    easy::HighlightLines,
+    highlighting::{FontStyle, ThemeSet},
+    parsing::SyntaxSet,
+};
+
+use crate::{Color, Renderer, Style, StyledLine, TextBoxConfig};
+
+/// The theme used in place of the unknown ones.
+const DEFAULT_THEME: &str = "base16-ocean.dark";
+
+/// The syntaxes and the themes bundled with `syntect`, loaded once.
+fn assets() -> &'static (SyntaxSet, ThemeSet) {
+    static ASSETS: OnceLock<(SyntaxSet, ThemeSet)> = OnceLock::new();
+    ASSETS.get_or_init(|| {
+        (
+            SyntaxSet::load_defaults_nonewlines(),
+            ThemeSet::load_defaults(),
+        )
+    })
+}
+
+/// Generate a message box displaying the code highlighted with the syntax of the given
+/// language, looked up by its name or file extension, and the theme bundled with `syntect`,
+/// like `"InspiredGitHub"` or `"Solarized (dark)"`. The unknown languages are displayed
+/// as plain text, and the unknown themes are replaced with `"base16-ocean.dark"`.
+/// With [`line_numbers`](TextBoxConfig::line_numbers), every line is prefixed with its number.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// let code = "fn main() {}\n";
+/// let config = TextBoxConfig::default().line_numbers();
+///
+/// assert!(box_code(code, "rs", "InspiredGitHub", config).contains("\x1b[38;2;"));
+/// assert_eq!(box_code(code, "rs", "InspiredGitHub", config.strip_styles()), "\
+/// ╭─────────────────╮
+/// │ 1  fn main() {} │
+/// ╰─────────────────╯");
+/// ```
+pub fn box_code(code: &str, language: &str, theme: &str, config: TextBoxConfig<'_>) -> String {
+    let (syntaxes, themes) = assets();
+    let syntax = syntaxes
+        .find_syntax_by_token(language)
+        .unwrap_or_else(|| syntaxes.find_syntax_plain_text());
+    let theme = themes
+        .themes
+        .get(theme)
+        .unwrap_or(&themes.themes[DEFAULT_THEME]);
+
+    let code = code.trim_end_matches('\n');
+    let count = code.lines().count();
+    let number_width = count.to_string().len();
+    let mut highlighter = HighlightLines::new(syntax, theme);
+    let lines = code
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            let mut styled = StyledLine::new();
+            if config.line_numbers {
+                let number = format!("{:>width$}  ", index + 1, width = number_width);
+                styled = styled.with_span(number, Style::new().dim());
+            }
+            // The regexes of the bundled syntaxes are valid, so highlighting never fails.
+            let ranges = highlighter
+                .highlight_line(line, syntaxes)
+                .unwrap_or_else(|_| vec![(Default::default(), line)]);
+            for (style, text) in ranges {
+                styled = styled.with_span(text, span_style(style));
+            }
+            styled
+        })
+        .collect::<Vec<_>>();
+
+    Renderer::new(config).render_styled(&lines)
+}
+
+/// The terminal style of a span highlighted by `syntect`. The backgrounds of the themes
+/// are dropped, since they're meant to fill the whole editor.
+fn span_style(style: syntect::highlighting::Style) -> Style {
+    let fg = style.foreground;
+    Style {
+        fg: Some(Color::Rgb(fg.r, fg.g, fg.b)),
+        bold: style.font_style.contains(FontStyle::BOLD),
+        italic: style.font_style.contains(FontStyle::ITALIC),
+        underline: style.font_style.contains(FontStyle::UNDERLINE),
+        ..Style::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_box_code() {
+        let config = TextBoxConfig::default().strip_styles();
+        let code = "let x = 1;\nlet y = 2;\n\n";
+        assert_eq!(
+            box_code(code, "no such language", "no such theme", config),
+            "\
+╭────────────╮
+│ let x = 1; │
+│ let y = 2; │
+╰────────────╯"
+        );
+
+        let code = (1..=10).map(|n| format!("x{}\n", n)).collect::<String>();
+        let msg = box_code(&code, "py", "InspiredGitHub", config.line_numbers());
+        assert_eq!(msg.lines().nth(1), Some("│  1  x1  │"));
+        assert_eq!(msg.lines().nth(10), Some("│ 10  x10 │"));
+    }
+}
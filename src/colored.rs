@@ -0,0 +1,82 @@
+//! Writing the colored boxes through [`termcolor`], enabled by the `termcolor` feature.
+use std::io;
+
+use termcolor::{ColorSpec, WriteColor};
+
+use crate::{Color, Renderer, Style, StyledLine, TextBoxConfig};
+
+/// Write a message box displaying the given rich text lines into a [`WriteColor`],
+/// switching the colors with [`ColorSpec`]s instead of the embedded escape sequences,
+/// so the colors work on the legacy Windows consoles as well.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// use termcolor::{Ansi, NoColor};
+///
+/// let lines = [StyledLine::new().with_span("ok", Style::new().fg(Color::Green))];
+///
+/// let mut plain = NoColor::new(Vec::new());
+/// write_colored(&mut plain, &lines, TextBoxConfig::default())?;
+/// assert_eq!(String::from_utf8(plain.into_inner()).unwrap(), "\
+/// ╭────╮
+/// │ ok │
+/// ╰────╯");
+///
+/// let mut ansi = Ansi::new(Vec::new());
+/// write_colored(&mut ansi, &lines, TextBoxConfig::default())?;
+/// assert!(String::from_utf8(ansi.into_inner()).unwrap().contains("\x1b[32mok\x1b[0m"));
+/// # std::io::Result::Ok(())
+/// ```
+pub fn write_colored<W: WriteColor>(
+    writer: &mut W,
+    lines: &[StyledLine<'_>],
+    config: TextBoxConfig<'_>,
+) -> io::Result<()> {
+    let msgbox = Renderer::new(config).render_styled(lines);
+    for (index, row) in msgbox.split('\n').enumerate() {
+        if index > 0 {
+            writer.write_all(b"\n")?;
+        }
+        for span in StyledLine::from_ansi(row).spans {
+            if span.style.is_plain() {
+                writer.write_all(span.text.as_bytes())?;
+            } else {
+                writer.set_color(&span.style.into())?;
+                writer.write_all(span.text.as_bytes())?;
+                writer.reset()?;
+            }
+        }
+    }
+    Ok(())
+}
+
+impl From<Color> for termcolor::Color {
+    fn from(color: Color) -> Self {
+        use termcolor::Color as C;
+        match color {
+            Color::Black => C::Black,
+            Color::Red => C::Red,
+            Color::Green => C::Green,
+            Color::Yellow => C::Yellow,
+            Color::Blue => C::Blue,
+            Color::Magenta => C::Magenta,
+            Color::Cyan => C::Cyan,
+            Color::White => C::White,
+            Color::Fixed(n) => C::Ansi256(n),
+            Color::Rgb(r, g, b) => C::Rgb(r, g, b),
+        }
+    }
+}
+
+impl From<Style> for ColorSpec {
+    fn from(style: Style) -> Self {
+        let mut spec = ColorSpec::new();
+        spec.set_fg(style.fg.map(Into::into))
+            .set_bg(style.bg.map(Into::into))
+            .set_bold(style.bold)
+            .set_dimmed(style.dim)
+            .set_italic(style.italic)
+            .set_underline(style.underline);
+        spec
+    }
+}
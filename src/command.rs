@@ -0,0 +1,124 @@
+//! Framing the results of the child processes, like the ones returned by `Command::output`.
+use std::process::Output;
+
+use crate::{generate_separated, line::INDENT_WIDTH, TextBoxConfig};
+
+/// The maximum number of lines of stdout and of stderr displayed by [`box_command_output`].
+const MAX_OUTPUT_LINES: usize = 100;
+
+/// Generate a message box with the exit status, the stdout and the stderr of a finished
+/// process, separated by horizontal rules. The streams are decoded as UTF-8, replacing
+/// the invalid sequences, and the empty ones are skipped. Only the last 100 lines of
+/// every stream are displayed. The box is captioned with `"output"` unless the config
+/// has a caption.
+///
+/// ```
+/// # #[cfg(unix)] {
+/// # use plain_msgbox::*;
+/// use std::os::unix::process::ExitStatusExt;
+/// use std::process::{ExitStatus, Output};
+///
+/// let output = Output {
+///     status: ExitStatus::from_raw(1 << 8),
+///     stdout: b"Compiling app\n".to_vec(),
+///     stderr: b"error: 1 test failed\n".to_vec(),
+/// };
+///
+/// assert_eq!(box_command_output(&output, TextBoxConfig::default()), "\
+/// ╭────────────────────────╮
+/// │ exit code: 1           │
+/// ├────────────────────────┤
+/// │ stdout:                │
+/// │   Compiling app        │
+/// ├────────────────────────┤
+/// │ stderr:                │
+/// │   error: 1 test failed │
+/// <output>─────────────────╯");
+/// # }
+/// ```
+pub fn box_command_output(output: &Output, config: TextBoxConfig<'_>) -> String {
+    let status = match output.status.code() {
+        Some(code) => format!("exit code: {}", code),
+        None => String::from("exit code: none, terminated by a signal"),
+    };
+    let status = [status];
+    let stdout = stream_lines("stdout", &output.stdout);
+    let stderr = stream_lines("stderr", &output.stderr);
+
+    let config = TextBoxConfig {
+        last_line_caption: config.last_line_caption.or(Some("output")),
+        ..config
+    };
+    generate_separated(&[&status, &stdout, &stderr], config)
+}
+
+/// The label of the stream followed by its indented lines,
+/// or nothing if the stream is empty.
+fn stream_lines(label: &str, bytes: &[u8]) -> Vec<String> {
+    let text = String::from_utf8_lossy(bytes);
+    let lines = text.trim_end().lines().collect::<Vec<_>>();
+    if lines.is_empty() {
+        return Vec::new();
+    }
+
+    let omitted = lines.len().saturating_sub(MAX_OUTPUT_LINES);
+    let mut result = Vec::with_capacity(lines.len() - omitted + 2);
+    result.push(format!("{}:", label));
+    if omitted > 0 {
+        result.push(format!(
+            "{:indent$}... {} lines omitted",
+            "",
+            omitted,
+            indent = INDENT_WIDTH
+        ));
+    }
+    result.extend(
+        lines[omitted..]
+            .iter()
+            .map(|line| format!("{:indent$}{}", "", line, indent = INDENT_WIDTH)),
+    );
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_lines() {
+        assert!(stream_lines("stdout", b"").is_empty());
+        assert!(stream_lines("stdout", b" \n\n").is_empty());
+        assert_eq!(
+            stream_lines("stderr", b"bad \xff byte\r\nnext"),
+            ["stderr:", "  bad \u{fffd} byte", "  next"]
+        );
+
+        let long = (0..105).map(|n| format!("{}\n", n)).collect::<String>();
+        let lines = stream_lines("stdout", long.as_bytes());
+        assert_eq!(lines.len(), MAX_OUTPUT_LINES + 2);
+        assert_eq!(lines[1], "  ... 5 lines omitted");
+        assert_eq!(lines[2], "  5");
+        assert_eq!(lines.last().unwrap(), "  104");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_box_command_output() {
+        use std::os::unix::process::ExitStatusExt;
+        use std::process::ExitStatus;
+
+        let output = Output {
+            status: ExitStatus::from_raw(9),
+            stdout: Vec::new(),
+            stderr: Vec::new(),
+        };
+        let config = TextBoxConfig::ascii().with_caption("make");
+        assert_eq!(
+            box_command_output(&output, config),
+            "\
++-----------------------------------------+
+| exit code: none, terminated by a signal |
+<make>------------------------------------+"
+        );
+    }
+}
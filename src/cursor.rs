@@ -0,0 +1,47 @@
+//! Printing the boxes at terminal coordinates, enabled by the `crossterm` feature.
+use std::io;
+
+use crossterm::{
+    cursor::{MoveTo, RestorePosition, SavePosition},
+    style::Print,
+    QueueableCommand,
+};
+
+use crate::{generate_with_config, TextBoxConfig};
+
+/// Print a message box with its top left corner at the given column and row of the terminal,
+/// restoring the cursor position afterwards. Useful for the status boxes drawn over the existing output.
+pub fn print_box_at(x: u16, y: u16, lines: &[String], config: TextBoxConfig<'_>) -> io::Result<()> {
+    write_box_at(io::stdout().lock(), x, y, lines, config)
+}
+
+/// Write the commands printing a message box at the given column and row into the given writer.
+/// See [`print_box_at`].
+///
+/// ```
+/// # use plain_msgbox::*;
+/// let mut output = Vec::new();
+/// write_box_at(&mut output, 4, 2, &[String::from("ok")], TextBoxConfig::default())?;
+///
+/// assert_eq!(
+///     String::from_utf8(output).unwrap(),
+///     "\x1b7\x1b[3;5H╭────╮\x1b[4;5H│ ok │\x1b[5;5H╰────╯\x1b8"
+/// );
+/// # std::io::Result::Ok(())
+/// ```
+pub fn write_box_at<W: io::Write>(
+    mut writer: W,
+    x: u16,
+    y: u16,
+    lines: &[String],
+    config: TextBoxConfig<'_>,
+) -> io::Result<()> {
+    let msgbox = generate_with_config(lines, config);
+
+    writer.queue(SavePosition)?;
+    for (row, line) in (y..).zip(msgbox.lines()) {
+        writer.queue(MoveTo(x, row))?.queue(Print(line))?;
+    }
+    writer.queue(RestorePosition)?;
+    writer.flush()
+}
@@ -0,0 +1,147 @@
+//! Documents made of several message boxes sharing one style.
+use crate::{Renderer, TextBoxConfig};
+
+/// A document made of several message boxes that share one style and one width.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// let report = BoxDocument::new(TextBoxConfig::default())
+///     .with_spacing(1)
+///     .section(vec![String::from("Tests passed: 42")])
+///     .section_with_caption(vec![String::from("Warnings: 0")], "Lints")
+///     .render();
+///
+/// assert_eq!(report, "\
+/// ╭──────────────────╮
+/// │ Tests passed: 42 │
+/// ╰──────────────────╯
+///
+/// ╭──────────────────╮
+/// │ Warnings: 0      │
+/// <Lints>────────────╯");
+/// ```
+#[derive(Debug, Clone)]
+pub struct BoxDocument<'a> {
+    config: TextBoxConfig<'a>,
+    sections: Vec<Section<'a>>,
+    spacing: usize,
+}
+
+/// A single box of a [`BoxDocument`].
+#[derive(Debug, Clone)]
+struct Section<'a> {
+    lines: Vec<String>,
+    caption: Option<&'a str>,
+}
+
+impl<'a> BoxDocument<'a> {
+    /// Create an empty document rendering every box with the given config.
+    /// The caption of the config is ignored in favor of the per-section captions.
+    pub fn new(config: TextBoxConfig<'a>) -> Self {
+        Self {
+            config,
+            sections: Vec::new(),
+            spacing: 0,
+        }
+    }
+
+    /// Separate the boxes with the given number of empty lines.
+    pub fn with_spacing(self, spacing: usize) -> Self {
+        Self { spacing, ..self }
+    }
+
+    /// Add a box with the given lines to the end of the document.
+    pub fn section(mut self, lines: Vec<String>) -> Self {
+        self.sections.push(Section {
+            lines,
+            caption: None,
+        });
+        self
+    }
+
+    /// Add a box with the given lines and caption to the end of the document.
+    pub fn section_with_caption(mut self, lines: Vec<String>, caption: &'a str) -> Self {
+        self.sections.push(Section {
+            lines,
+            caption: Some(caption),
+        });
+        self
+    }
+
+    /// Render all of the boxes at the width of the widest one.
+    pub fn render(&self) -> String {
+        let renderers = self
+            .sections
+            .iter()
+            .map(|section| {
+                Renderer::new(TextBoxConfig {
+                    last_line_caption: section.caption,
+                    ..self.config
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let width = renderers
+            .iter()
+            .zip(&self.sections)
+            .map(|(renderer, section)| renderer.measure(&section.lines))
+            .max()
+            .unwrap_or(0);
+
+        let line_ending = self.config.line_ending;
+        let separator = line_ending.repeat(self.spacing + 1);
+        let mut document = renderers
+            .into_iter()
+            .zip(&self.sections)
+            .map(|(renderer, section)| {
+                let config = TextBoxConfig {
+                    trailing_newline: false,
+                    ..*renderer.config()
+                };
+                Renderer::with_width(config, width).render(&section.lines)
+            })
+            .collect::<Vec<_>>()
+            .join(&separator);
+        if self.config.trailing_newline && !document.is_empty() {
+            document.push_str(line_ending);
+        }
+        document
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_document() {
+        assert_eq!(BoxDocument::new(TextBoxConfig::default()).render(), "");
+
+        let document = BoxDocument::new(TextBoxConfig::dos().with_caption("ignored"))
+            .section_with_caption(vec![], "A long caption")
+            .section(vec!["short".to_string()]);
+
+        assert_eq!(
+            document.render(),
+            "\
+╔════════════════╗
+<A long caption>═╝
+╔════════════════╗
+║ short          ║
+╚════════════════╝"
+        );
+        assert_eq!(
+            document.with_spacing(2).render().matches("\n\n\n╔").count(),
+            1
+        );
+
+        let document = BoxDocument::new(TextBoxConfig::ascii().trailing_newline())
+            .section(vec!["a".to_string()])
+            .section(vec!["b".to_string()])
+            .with_spacing(1);
+        assert_eq!(
+            document.render(),
+            "+---+\n| a |\n+---+\n\n+---+\n| b |\n+---+\n"
+        );
+    }
+}
@@ -0,0 +1,139 @@
+//! Checking and downgrading the characters of the configs for the sinks that only handle ASCII.
+use crate::{ascii, BorderRow, TextBoxConfig};
+
+/// The rows of the custom borders checked by [`is_ascii_safe`].
+const CHECKED_ROWS: [BorderRow; 4] = [
+    BorderRow::Top,
+    BorderRow::Content,
+    BorderRow::Separator,
+    BorderRow::Bottom,
+];
+
+/// Whether the box drawn with the given config only contains ASCII characters besides the lines
+/// and the captions: the glyphs of the border, the column divider, the padding fill, the row
+/// decorators, the ellipsis, and the segments of the custom [`Border`](crate::Border), if any.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// assert!(is_ascii_safe(&TextBoxConfig::ascii()));
+/// assert!(!is_ascii_safe(&TextBoxConfig::default()));
+/// assert!(is_ascii_safe(&to_ascii_fallback(&TextBoxConfig::dos())));
+/// ```
+pub fn is_ascii_safe(config: &TextBoxConfig<'_>) -> bool {
+    let glyphs = [
+        config.horizontal_bar,
+        config.vertical_bar,
+        config.left_top_corner,
+        config.left_bottom_corner,
+        config.right_top_corner,
+        config.right_bottom_corner,
+        config.left_tee,
+        config.right_tee,
+        config.tail,
+        config.column_divider,
+        config.row_prefix,
+        config.row_suffix,
+        config.padding_fill.unwrap_or_default(),
+        config.ellipsis.unwrap_or_default(),
+    ];
+    glyphs.iter().all(|glyph| glyph.is_ascii())
+        && config.border.is_none_or(|border| {
+            CHECKED_ROWS.iter().all(|&row| {
+                let segments = border.segments(row, 1);
+                segments.left.is_ascii() && segments.fill.is_ascii() && segments.right.is_ascii()
+            })
+        })
+}
+
+/// Replace the characters that make the config fail [`is_ascii_safe`] with their ASCII
+/// counterparts from [`TextBoxConfig::ascii`]. The row decorators and the padding fill
+/// with other characters are removed, and so is a custom border that isn't ASCII safe.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// let config = to_ascii_fallback(&TextBoxConfig::default().with_caption("log"));
+///
+/// assert_eq!(generate_with_config(&[String::from("ok")], config), "\
+/// +-----+
+/// | ok  |
+/// <log>-+");
+/// ```
+pub fn to_ascii_fallback<'a>(config: &TextBoxConfig<'a>) -> TextBoxConfig<'a> {
+    let glyph = |glyph: &'a str, fallback: &'a str| {
+        if glyph.is_ascii() {
+            glyph
+        } else {
+            fallback
+        }
+    };
+    let border = config.border.filter(|_| {
+        is_ascii_safe(&TextBoxConfig {
+            border: config.border,
+            ..TextBoxConfig::ascii()
+        })
+    });
+
+    TextBoxConfig {
+        horizontal_bar: glyph(config.horizontal_bar, ascii::ASCII_HORIZONTAL_BAR),
+        vertical_bar: glyph(config.vertical_bar, ascii::ASCII_VERTICAL_BAR),
+        left_top_corner: glyph(config.left_top_corner, ascii::ASCII_CORNER),
+        left_bottom_corner: glyph(config.left_bottom_corner, ascii::ASCII_CORNER),
+        right_top_corner: glyph(config.right_top_corner, ascii::ASCII_CORNER),
+        right_bottom_corner: glyph(config.right_bottom_corner, ascii::ASCII_CORNER),
+        left_tee: glyph(config.left_tee, ascii::ASCII_CORNER),
+        right_tee: glyph(config.right_tee, ascii::ASCII_CORNER),
+        tail: glyph(config.tail, ascii::ASCII_TAIL),
+        column_divider: glyph(config.column_divider, ascii::ASCII_VERTICAL_BAR),
+        row_prefix: glyph(config.row_prefix, ""),
+        row_suffix: glyph(config.row_suffix, ""),
+        padding_fill: config.padding_fill.filter(|fill| fill.is_ascii()),
+        ellipsis: config
+            .ellipsis
+            .map(|ellipsis| glyph(ellipsis, ascii::ASCII_ELLIPSIS)),
+        border,
+        ..*config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Border, BorderSegments};
+
+    struct Stars;
+
+    impl Border for Stars {
+        fn segments(&self, _: BorderRow, _: usize) -> BorderSegments<'_> {
+            BorderSegments {
+                left: "✦".into(),
+                fill: "*".into(),
+                right: "✦".into(),
+            }
+        }
+    }
+
+    #[test]
+    fn test_ascii_fallback() {
+        let config = TextBoxConfig::dos()
+            .with_row_decorators("> ", " ▐")
+            .with_padding_fill("·");
+        assert!(!is_ascii_safe(&config));
+
+        let fallback = to_ascii_fallback(&config);
+        assert!(is_ascii_safe(&fallback));
+        assert_eq!(fallback.row_prefix, "> ");
+        assert_eq!(fallback.row_suffix, "");
+        assert_eq!(fallback.padding_fill, None);
+        assert_eq!(fallback.vertical_bar, "|");
+
+        let border = Stars;
+        let config = TextBoxConfig::ascii().with_border(&border);
+        assert!(!is_ascii_safe(&config));
+        assert!(to_ascii_fallback(&config).border.is_none());
+
+        let border = TextBoxConfig::ascii();
+        let config = TextBoxConfig::ascii().with_border(&border);
+        assert!(is_ascii_safe(&config));
+        assert!(to_ascii_fallback(&config).border.is_some());
+    }
+}
@@ -0,0 +1,135 @@
+//! The C interface, enabled by the `ffi` feature. The header is generated into
+//! `include/plain_msgbox.h` by the build script.
+//!
+//! ```c
+//! const char *lines[] = {"Call stack size: 1024", "Optimization level: 1"};
+//! PlainMsgboxOptions options = {.caption = "Config", .dos = false, .width = 0};
+//! char *msgbox = plain_msgbox_generate(lines, 2, &options);
+//! puts(msgbox);
+//! plain_msgbox_free(msgbox);
+//! ```
+use std::{
+    ffi::{CStr, CString},
+    os::raw::c_char,
+    ptr, slice,
+};
+
+use crate::{Renderer, TextBoxConfig};
+
+/// The options of the generated box.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct PlainMsgboxOptions {
+    /// The caption attached to the bottom border, or null.
+    pub caption: *const c_char,
+    /// Use the DOS box drawing characters.
+    pub dos: bool,
+    /// The fixed width of the box, borders included, or zero to fit the content.
+    pub width: usize,
+}
+
+/// Generate a message box displaying the given UTF-8 lines.
+/// If `options` is null, the box has the default style and no caption.
+///
+/// Returns null if any of the strings isn't valid UTF-8. The returned string
+/// must be freed with [`plain_msgbox_free`].
+///
+/// # Safety
+/// `lines` must point to `count` valid null-terminated strings, and `options` must be
+/// either null or a valid pointer whose caption is either null or a valid null-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn plain_msgbox_generate(
+    lines: *const *const c_char,
+    count: usize,
+    options: *const PlainMsgboxOptions,
+) -> *mut c_char {
+    let lines = if count == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(lines, count)
+    };
+    let lines = match lines
+        .iter()
+        .map(|&line| to_str(line).map(String::from))
+        .collect::<Option<Vec<_>>>()
+    {
+        Some(lines) => lines,
+        None => return ptr::null_mut(),
+    };
+
+    let options = options.as_ref();
+    let mut config = match options {
+        Some(options) if options.dos => TextBoxConfig::dos(),
+        _ => TextBoxConfig::default(),
+    };
+    if let Some(caption) = options.map(|options| options.caption) {
+        if !caption.is_null() {
+            match to_str(caption) {
+                Some(caption) => config.last_line_caption = Some(caption),
+                None => return ptr::null_mut(),
+            }
+        }
+    }
+
+    let renderer = match options.map_or(0, |options| options.width) {
+        0 => Renderer::new(config),
+        width => Renderer::with_width(config, width),
+    };
+    CString::new(renderer.render(&lines)).map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// Free a string returned by [`plain_msgbox_generate`]. Does nothing if the string is null.
+///
+/// # Safety
+/// The string must have been returned by [`plain_msgbox_generate`] and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn plain_msgbox_free(msgbox: *mut c_char) {
+    if !msgbox.is_null() {
+        drop(CString::from_raw(msgbox));
+    }
+}
+
+/// Borrow the given null-terminated string if it's valid UTF-8.
+unsafe fn to_str<'s>(text: *const c_char) -> Option<&'s str> {
+    CStr::from_ptr(text).to_str().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate() {
+        let lines = [CString::new("abc").unwrap(), CString::new("ü").unwrap()];
+        let pointers = lines.iter().map(|line| line.as_ptr()).collect::<Vec<_>>();
+        let caption = CString::new("C").unwrap();
+        let options = PlainMsgboxOptions {
+            caption: caption.as_ptr(),
+            dos: true,
+            width: 0,
+        };
+
+        unsafe {
+            let msgbox = plain_msgbox_generate(pointers.as_ptr(), pointers.len(), &options);
+            assert_eq!(
+                CStr::from_ptr(msgbox).to_str().unwrap(),
+                crate::generate_with_config(
+                    &["abc".to_string(), "ü".to_string()],
+                    TextBoxConfig::dos().with_caption("C")
+                )
+            );
+            plain_msgbox_free(msgbox);
+
+            let msgbox = plain_msgbox_generate(ptr::null(), 0, ptr::null());
+            assert_eq!(
+                CStr::from_ptr(msgbox).to_str().unwrap(),
+                crate::generate_box(&[])
+            );
+            plain_msgbox_free(msgbox);
+
+            let invalid = [0xffu8, 0];
+            let pointers = [invalid.as_ptr() as *const c_char];
+            assert!(plain_msgbox_generate(pointers.as_ptr(), 1, ptr::null()).is_null());
+        }
+    }
+}
@@ -0,0 +1,91 @@
+//! The multi-column layout of short items, see [`generate_flow`](crate::generate_flow).
+use crate::{measure::Measure, renderer::is_numeric, spacer::push_spaces};
+
+/// The number of spaces between the columns.
+const COLUMN_GAP: usize = 2;
+
+/// Flow the items into as many columns as fit into `max_width`, filling the columns
+/// from top to bottom like `ls` does. Returns the resulting lines without the trailing padding.
+/// If `align_numbers` is set, the numeric items are right-aligned within their columns.
+pub(crate) fn flow(
+    items: &[&str],
+    max_width: usize,
+    measure: Measure<'_>,
+    align_numbers: bool,
+) -> Vec<String> {
+    let widths = items
+        .iter()
+        .map(|item| measure.width(item))
+        .collect::<Vec<_>>();
+
+    let (height, column_widths) = (1..=items.len())
+        .rev()
+        .map(|columns| {
+            let height = items.len().div_ceil(columns);
+            let column_widths = widths
+                .chunks(height)
+                .map(|column| column.iter().copied().max().unwrap_or(0))
+                .collect::<Vec<_>>();
+            (height, column_widths)
+        })
+        .find(|(_, column_widths)| {
+            let gaps = COLUMN_GAP * (column_widths.len() - 1);
+            column_widths.iter().sum::<usize>() + gaps <= max_width
+        })
+        .unwrap_or_else(|| (items.len(), vec![widths.iter().copied().max().unwrap_or(0)]));
+
+    (0..height)
+        .map(|row| {
+            let mut line = String::new();
+            let cells = (row..items.len()).step_by(height).zip(&column_widths);
+            for (index, width) in cells {
+                if index >= height {
+                    push_spaces(&mut line, COLUMN_GAP);
+                }
+                let padding = width - widths[index];
+                if align_numbers && is_numeric(items[index]) {
+                    push_spaces(&mut line, padding);
+                    line.push_str(items[index]);
+                } else {
+                    line.push_str(items[index]);
+                    push_spaces(&mut line, padding);
+                }
+            }
+            line.truncate(line.trim_end_matches(' ').len());
+            line
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ByteWidth;
+
+    #[test]
+    fn test_flow() {
+        let items = ["a", "bbb", "cc", "d", "eeeee", "f", "g"];
+
+        assert_eq!(
+            flow(&items, 100, &ByteWidth, false),
+            ["a  bbb  cc  d  eeeee  f  g"]
+        );
+        assert_eq!(
+            flow(&items, 17, &ByteWidth, false),
+            ["a    cc  eeeee  g", "bbb  d   f"]
+        );
+        assert_eq!(
+            flow(&items, 16, &ByteWidth, false),
+            ["a    d      g", "bbb  eeeee", "cc   f"]
+        );
+        assert_eq!(
+            flow(&items, 12, &ByteWidth, false),
+            ["a    eeeee", "bbb  f", "cc   g", "d"]
+        );
+        assert_eq!(flow(&items, 1, &ByteWidth, false), items);
+        assert!(flow(&[], 10, &ByteWidth, false).is_empty());
+
+        let sizes = ["1", "512", "64", "x"];
+        assert_eq!(flow(&sizes, 8, &ByteWidth, true), ["  1  64", "512  x"]);
+    }
+}
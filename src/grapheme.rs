@@ -0,0 +1,113 @@
+//! The boundaries of the grapheme clusters the text is never split within.
+//!
+//! The clusters are approximated without the Unicode data tables: a character is joined
+//! with the one before it if it's a combining mark, a variation selector, an emoji modifier
+//! or tag, if it follows a zero width joiner, or if it completes a pair of regional indicators.
+
+/// The zero width joiner gluing the emoji sequences together.
+const ZWJ: char = '\u{200d}';
+
+/// The ranges of the characters that extend the cluster of the character before them.
+const EXTENDING: &[(char, char)] = &[
+    ('\u{0300}', '\u{036f}'),
+    ('\u{0483}', '\u{0489}'),
+    ('\u{0591}', '\u{05bd}'),
+    ('\u{0610}', '\u{061a}'),
+    ('\u{064b}', '\u{065f}'),
+    ('\u{0900}', '\u{0903}'),
+    ('\u{093a}', '\u{094f}'),
+    ('\u{0e31}', '\u{0e31}'),
+    ('\u{0e34}', '\u{0e3a}'),
+    ('\u{0e47}', '\u{0e4e}'),
+    ('\u{1160}', '\u{11ff}'),
+    ('\u{1ab0}', '\u{1aff}'),
+    ('\u{1dc0}', '\u{1dff}'),
+    ('\u{200c}', '\u{200d}'),
+    ('\u{20d0}', '\u{20ff}'),
+    ('\u{302a}', '\u{302f}'),
+    ('\u{3099}', '\u{309a}'),
+    ('\u{fe00}', '\u{fe0f}'),
+    ('\u{fe20}', '\u{fe2f}'),
+    ('\u{1f3fb}', '\u{1f3ff}'),
+    ('\u{e0020}', '\u{e007f}'),
+    ('\u{e0100}', '\u{e01ef}'),
+];
+
+fn is_extending(c: char) -> bool {
+    !c.is_ascii()
+        && EXTENDING
+            .iter()
+            .any(|&(start, end)| (start..=end).contains(&c))
+}
+
+fn is_regional_indicator(c: char) -> bool {
+    ('\u{1f1e6}'..='\u{1f1ff}').contains(&c)
+}
+
+/// Whether a cluster can't start at the given char boundary of the text.
+fn is_inside(text: &str, index: usize) -> bool {
+    let (before, after) = text.split_at(index);
+    let (c, previous) = match (after.chars().next(), before.chars().next_back()) {
+        (Some(c), Some(previous)) => (c, previous),
+        _ => return false,
+    };
+    if is_extending(c) || previous == ZWJ {
+        return true;
+    }
+    is_regional_indicator(c)
+        && before
+            .chars()
+            .rev()
+            .take_while(|&c| is_regional_indicator(c))
+            .count()
+            % 2
+            == 1
+}
+
+/// Move the char boundary of the text back to the start of the cluster it's in.
+pub(crate) fn floor_boundary(text: &str, mut index: usize) -> usize {
+    while is_inside(text, index) {
+        index = text[..index]
+            .char_indices()
+            .next_back()
+            .map_or(0, |(i, _)| i);
+    }
+    index
+}
+
+/// The length of the first cluster of the text.
+pub(crate) fn first_len(text: &str) -> usize {
+    text.char_indices()
+        .skip(1)
+        .map(|(index, _)| index)
+        .find(|&index| !is_inside(text, index))
+        .unwrap_or(text.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_boundaries() {
+        let text = "e\u{301}x";
+        assert_eq!(floor_boundary(text, 1), 0);
+        assert_eq!(floor_boundary(text, 3), 3);
+        assert_eq!(first_len(text), 3);
+
+        let family = "👨\u{200d}👩\u{200d}👧!";
+        assert_eq!(floor_boundary(family, 4), 0);
+        assert_eq!(floor_boundary(family, 11), 0);
+        assert_eq!(first_len(family), family.len() - 1);
+
+        let flags = "🇫🇷🇩🇪";
+        assert_eq!(floor_boundary(flags, 4), 0);
+        assert_eq!(floor_boundary(flags, 8), 8);
+        assert_eq!(floor_boundary(flags, 12), 8);
+        assert_eq!(first_len(flags), 8);
+
+        assert_eq!(floor_boundary("abc", 2), 2);
+        assert_eq!(first_len(""), 0);
+        assert_eq!(floor_boundary("ab", 2), 2);
+    }
+}
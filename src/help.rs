@@ -0,0 +1,69 @@
+//! Framing the help text of command line tools, like the help rendered by `clap`.
+use crate::{generate_separated, generate_with_config, TextBoxConfig};
+
+/// Generate a message box framing the given help text, like the one rendered by
+/// `clap::Command::render_help`. The trailing whitespace of the text is removed.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// let help = "Usage: app [OPTIONS]\n\nOptions:\n  -v  Verbose\n";
+///
+/// assert_eq!(generate_help(help, TextBoxConfig::default()), "\
+/// ╭──────────────────────╮
+/// │ Usage: app [OPTIONS] │
+/// │                      │
+/// │ Options:             │
+/// │   -v  Verbose        │
+/// ╰──────────────────────╯");
+/// ```
+pub fn generate_help(help: &str, config: TextBoxConfig<'_>) -> String {
+    let lines = help
+        .trim_end()
+        .lines()
+        .map(String::from)
+        .collect::<Vec<_>>();
+    generate_with_config(&lines, config)
+}
+
+/// Generate a message box framing the given help text with a horizontal rule between its
+/// sections, such as the usage, the arguments, the options, and the subcommands.
+/// The sections are separated by the blank lines of the text.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// let help = "\
+/// A tool
+///
+/// Usage: app [OPTIONS] <COMMAND>
+///
+/// Commands:
+///   run   Run the tool
+///
+/// Options:
+///   -v  Verbose
+/// ";
+///
+/// assert_eq!(generate_help_sections(help, TextBoxConfig::default()), "\
+/// ╭────────────────────────────────╮
+/// │ A tool                         │
+/// ├────────────────────────────────┤
+/// │ Usage: app [OPTIONS] <COMMAND> │
+/// ├────────────────────────────────┤
+/// │ Commands:                      │
+/// │   run   Run the tool           │
+/// ├────────────────────────────────┤
+/// │ Options:                       │
+/// │   -v  Verbose                  │
+/// ╰────────────────────────────────╯");
+/// ```
+pub fn generate_help_sections(help: &str, config: TextBoxConfig<'_>) -> String {
+    let lines = help
+        .trim_end()
+        .lines()
+        .map(String::from)
+        .collect::<Vec<_>>();
+    let sections = lines
+        .split(|line| line.trim().is_empty())
+        .collect::<Vec<_>>();
+    generate_separated(&sections, config)
+}
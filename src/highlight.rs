@@ -0,0 +1,179 @@
+//! Highlighting the parts of the content lines, like the matches of regular expressions.
+use std::ops::Range;
+
+use crate::{Style, StyledLine};
+
+/// The marks placed around the highlighted parts of the lines when the styles are stripped.
+const MARK: &str = "*";
+
+/// Finds the parts of every content line to highlight, see [`TextBoxConfig::with_highlighter`](crate::TextBoxConfig::with_highlighter).
+///
+/// ```
+/// # use plain_msgbox::*;
+/// use std::ops::Range;
+///
+/// /// Highlights the first word of every line.
+/// struct FirstWord;
+///
+/// impl Highlighter for FirstWord {
+///     fn highlights(&self, line: &str) -> Vec<(Range<usize>, Style)> {
+///         let end = line.find(' ').unwrap_or(line.len());
+///         vec![(0..end, Style::new().bold())]
+///     }
+/// }
+///
+/// let lines = [String::from("ERROR disk full")];
+/// let config = TextBoxConfig::ascii().with_highlighter(&FirstWord);
+/// assert_eq!(generate_with_config(&lines, config), "\
+/// +-----------------+
+/// | \x1b[1mERROR\x1b[0m disk full |
+/// +-----------------+");
+/// assert_eq!(generate_with_config(&lines, config.strip_styles()), "\
+/// +-------------------+
+/// | *ERROR* disk full |
+/// +-------------------+");
+/// ```
+pub trait Highlighter {
+    /// The byte ranges of the line to highlight with their styles, in order and without overlaps.
+    fn highlights(&self, line: &str) -> Vec<(Range<usize>, Style)>;
+}
+
+/// Split the line into the spans of the given highlights and the unstyled text between them.
+/// With `marked`, the highlights are surrounded by the marks instead of being styled.
+pub(crate) fn apply<'l>(
+    line: &'l str,
+    highlights: Vec<(Range<usize>, Style)>,
+    marked: bool,
+) -> StyledLine<'l> {
+    let mut styled = StyledLine::new();
+    let mut end = 0;
+    for (range, style) in highlights {
+        let valid =
+            end <= range.start && range.start < range.end && line.get(range.clone()).is_some();
+        if !valid {
+            continue;
+        }
+
+        if end < range.start {
+            styled = styled.with_span(&line[end..range.start], Style::new());
+        }
+        if marked {
+            styled = styled.with_span(MARK, Style::new());
+            styled = styled.with_span(&line[range.clone()], Style::new());
+            styled = styled.with_span(MARK, Style::new());
+        } else {
+            styled = styled.with_span(&line[range.clone()], style);
+        }
+        end = range.end;
+    }
+    if end < line.len() {
+        styled = styled.with_span(&line[end..], Style::new());
+    }
+    styled
+}
+
+/// Highlights the matches of regular expressions, each in its own style.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// let highlighter = RegexHighlighter::new()
+///     .with_pattern(r"E\d{4}", Style::new().fg(Color::Red))?
+///     .with_pattern(r"#\d+", Style::new().bold())?;
+/// let lines = [String::from("E0425 in request #17")];
+/// let config = TextBoxConfig::default().with_highlighter(&highlighter);
+///
+/// assert_eq!(generate_with_config(&lines, config).lines().nth(1), Some(
+///     "│ \x1b[31mE0425\x1b[0m in request \x1b[1m#17\x1b[0m │"
+/// ));
+/// # Ok::<(), regex::Error>(())
+/// ```
+#[cfg(feature = "regex")]
+#[derive(Debug, Clone, Default)]
+pub struct RegexHighlighter {
+    patterns: Vec<(regex::Regex, Style)>,
+}
+
+#[cfg(feature = "regex")]
+impl RegexHighlighter {
+    /// Create a highlighter without any patterns.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Highlight the matches of the given regular expression in the given style.
+    /// Where the matches overlap, the patterns added first win.
+    pub fn with_regex(mut self, regex: regex::Regex, style: Style) -> Self {
+        self.patterns.push((regex, style));
+        self
+    }
+
+    /// Compile the given pattern and highlight its matches in the given style,
+    /// see [`with_regex`](Self::with_regex).
+    pub fn with_pattern(self, pattern: &str, style: Style) -> Result<Self, regex::Error> {
+        Ok(self.with_regex(regex::Regex::new(pattern)?, style))
+    }
+}
+
+#[cfg(feature = "regex")]
+impl Highlighter for RegexHighlighter {
+    fn highlights(&self, line: &str) -> Vec<(Range<usize>, Style)> {
+        let mut highlights: Vec<(Range<usize>, Style)> = Vec::new();
+        for (regex, style) in &self.patterns {
+            for found in regex.find_iter(line).filter(|found| !found.is_empty()) {
+                let range = found.range();
+                let overlaps = highlights
+                    .iter()
+                    .any(|(other, _)| range.start < other.end && other.start < range.end);
+                if !overlaps {
+                    highlights.push((range, *style));
+                }
+            }
+        }
+        highlights.sort_by_key(|(range, _)| range.start);
+        highlights
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply() {
+        let bold = Style::new().bold();
+        let line = apply(
+            "a bc d",
+            vec![(2..4, bold), (3..5, bold), (6..9, bold)],
+            false,
+        );
+        assert_eq!(
+            line,
+            StyledLine::new()
+                .with_span("a ", Style::new())
+                .with_span("bc", bold)
+                .with_span(" d", Style::new())
+        );
+
+        let line = apply("ab", vec![(0..1, bold)], true);
+        assert_eq!(line.text(), "*a*b");
+        assert_eq!(apply("é", vec![(1..2, bold)], false).text(), "é");
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_regex_highlighter() {
+        let red = Style::new().fg(crate::Color::Red);
+        let bold = Style::new().bold();
+        let highlighter = RegexHighlighter::new()
+            .with_pattern("id=\\d+", red)
+            .unwrap()
+            .with_pattern("\\d+|x*", bold)
+            .unwrap();
+
+        assert_eq!(
+            highlighter.highlights("id=12 took 30ms"),
+            [(0..5, red), (11..13, bold)]
+        );
+        assert!(RegexHighlighter::new().with_pattern("(", red).is_err());
+    }
+}
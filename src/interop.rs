@@ -0,0 +1,114 @@
+//! The conversions of the values styled by the `colored` and `owo-colors` crates into the styled lines.
+#[cfg(feature = "owo-colors")]
+use std::fmt;
+
+use crate::StyledLine;
+#[cfg(feature = "colored")]
+use crate::{Color, Style};
+
+/// Keeps the text and the styles of the colored string, regardless of whether `colored`
+/// would emit the escape sequences for the current terminal.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// use colored::Colorize;
+///
+/// let line = StyledLine::from("status: ".normal()).with_span("ok", Style::new());
+/// let renderer = Renderer::new(TextBoxConfig::default());
+/// let msg = renderer.render_styled(&[StyledLine::from("failed".red().bold()), line]);
+///
+/// assert_eq!(msg.lines().next(), Some("╭────────────╮"));
+/// assert_eq!(msg.lines().nth(1), Some("│ \x1b[1;31mfailed\x1b[0m     │"));
+/// ```
+#[cfg(feature = "colored")]
+impl From<colored::ColoredString> for StyledLine<'static> {
+    fn from(string: colored::ColoredString) -> Self {
+        let flags = string.style;
+        let style = Style {
+            fg: string.fgcolor.map(color),
+            bg: string.bgcolor.map(color),
+            bold: flags.contains(colored::Styles::Bold),
+            dim: flags.contains(colored::Styles::Dimmed),
+            italic: flags.contains(colored::Styles::Italic),
+            underline: flags.contains(colored::Styles::Underline),
+        };
+        StyledLine::new().with_span(string.input, style)
+    }
+}
+
+/// The color of the terminal matching the `colored` color. The bright colors are the
+/// upper half of the basic colors of the 256-color palette.
+#[cfg(feature = "colored")]
+fn color(color: colored::Color) -> Color {
+    use colored::Color as C;
+    match color {
+        C::Black => Color::Black,
+        C::Red => Color::Red,
+        C::Green => Color::Green,
+        C::Yellow => Color::Yellow,
+        C::Blue => Color::Blue,
+        C::Magenta => Color::Magenta,
+        C::Cyan => Color::Cyan,
+        C::White => Color::White,
+        C::BrightBlack => Color::Fixed(8),
+        C::BrightRed => Color::Fixed(9),
+        C::BrightGreen => Color::Fixed(10),
+        C::BrightYellow => Color::Fixed(11),
+        C::BrightBlue => Color::Fixed(12),
+        C::BrightMagenta => Color::Fixed(13),
+        C::BrightCyan => Color::Fixed(14),
+        C::BrightWhite => Color::Fixed(15),
+        C::AnsiColor(n) => Color::Fixed(n),
+        C::TrueColor { r, g, b } => Color::Rgb(r, g, b),
+    }
+}
+
+/// Parses the escape sequences written by the styled value, see [`StyledLine::from_display`].
+/// The other displays of `owo-colors`, like the ones returned by `OwoColorize::red`,
+/// can be converted with [`StyledLine::from_display`] directly.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// use owo_colors::OwoColorize;
+///
+/// let style = owo_colors::Style::new().green().bold();
+/// let line = StyledLine::from("up".style(style));
+///
+/// assert_eq!(line, StyledLine::new().with_span("up", Style::new().fg(Color::Green).bold()));
+/// ```
+#[cfg(feature = "owo-colors")]
+impl<T: fmt::Display> From<owo_colors::Styled<T>> for StyledLine<'static> {
+    fn from(styled: owo_colors::Styled<T>) -> Self {
+        StyledLine::from_display(styled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "colored")]
+    #[test]
+    fn test_colored() {
+        use colored::Colorize;
+
+        let line = StyledLine::from("x".bright_blue().on_truecolor(1, 2, 3).italic());
+        let style = Style::new()
+            .fg(Color::Fixed(12))
+            .bg(Color::Rgb(1, 2, 3))
+            .italic();
+        assert_eq!(line, StyledLine::new().with_span("x", style));
+    }
+
+    #[cfg(feature = "owo-colors")]
+    #[test]
+    fn test_owo_colors() {
+        use owo_colors::OwoColorize;
+
+        let line = StyledLine::from_display("x".bright_red().on_blue());
+        let style = crate::Style::new()
+            .fg(crate::Color::Fixed(9))
+            .bg(crate::Color::Blue);
+        assert_eq!(line, StyledLine::new().with_span("x", style));
+    }
+}
@@ -0,0 +1,54 @@
+//! The JSON encoding of the layout descriptions, see [`Renderer::render_json`](crate::Renderer::render_json).
+use std::fmt::Write;
+
+use crate::{Alignment, RowKind};
+
+/// Append the given text as a JSON string literal.
+pub(crate) fn push_string(out: &mut String, text: &str) {
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                write!(out, "\\u{:04x}", c as u32).expect("writing to a string never fails")
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// The name of the row kind in the layout description.
+pub(crate) fn row_kind(kind: RowKind) -> &'static str {
+    match kind {
+        RowKind::Border => "border",
+        RowKind::Content => "content",
+        RowKind::Separator => "separator",
+        RowKind::Caption => "caption",
+        RowKind::Tail => "tail",
+    }
+}
+
+/// The name of the alignment in the layout description.
+pub(crate) fn alignment(alignment: Alignment) -> &'static str {
+    match alignment {
+        Alignment::Left => "left",
+        Alignment::Center => "center",
+        Alignment::Right => "right",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_string() {
+        let mut out = String::new();
+        push_string(&mut out, "a\"b\\c\nd\x1b[0m│");
+        assert_eq!(out, r#""a\"b\\c\nd\u001b[0m│""#);
+    }
+}
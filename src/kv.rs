@@ -0,0 +1,280 @@
+//! The key-value layout of [`generate_key_values`].
+use std::{borrow::Cow, fmt};
+
+use crate::{
+    generate_key_values, line::INDENT_WIDTH, measure::Measure, spacer::push_spaces, wrap::wrap,
+    TextBoxConfig,
+};
+
+/// An entry of a key-value box, nested under the closest entry before it with a lower level.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// let entry = KeyValue::new("Optimization level:", "1");
+/// assert_eq!(entry.value.as_deref(), Some("1"));
+///
+/// let header = KeyValue::header("Optimizations:");
+/// let nested = KeyValue::new("Constant Folding:", "true").with_level(1);
+/// assert_eq!((header.value, nested.level), (None, 1));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyValue<'t> {
+    /// The key, displayed as is and indented by its level.
+    pub key: Cow<'t, str>,
+    /// The value, or `None` for the headers of the nested entries.
+    pub value: Option<Cow<'t, str>>,
+    /// The nesting level, every level is indented by two spaces.
+    pub level: usize,
+}
+
+impl<'t> KeyValue<'t> {
+    /// Create a top-level entry with the given key and value.
+    pub fn new(key: impl Into<Cow<'t, str>>, value: impl Into<Cow<'t, str>>) -> Self {
+        Self {
+            key: key.into(),
+            value: Some(value.into()),
+            level: 0,
+        }
+    }
+
+    /// Create a top-level entry without a value, heading the nested entries after it.
+    pub fn header(key: impl Into<Cow<'t, str>>) -> Self {
+        Self {
+            key: key.into(),
+            value: None,
+            level: 0,
+        }
+    }
+
+    /// Nest the entry at the given level.
+    pub fn with_level(self, level: usize) -> Self {
+        Self { level, ..self }
+    }
+}
+
+/// A value displayed as a key-value box, like the configs and the reports.
+/// With the `derive` feature, `#[derive(MsgBox)]` implements it for the structs with named
+/// fields, displaying the fields with `Display` under their names: `call_stack_size`
+/// becomes `Call stack size:`. The fields accept the `#[msgbox(rename = "Key")]`,
+/// `#[msgbox(skip)]`, and `#[msgbox(nested)]` attributes, the last of which displays
+/// the fields of a nested struct implementing `MsgBox` indented under its key.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// struct Status {
+///     healthy: bool,
+/// }
+///
+/// impl MsgBox for Status {
+///     fn entries(&self, level: usize) -> Vec<KeyValue<'static>> {
+///         vec![KeyValue::new("Healthy:", self.healthy.to_string()).with_level(level)]
+///     }
+/// }
+///
+/// assert_eq!(Status { healthy: true }.to_msgbox(TextBoxConfig::default()), "\
+/// ╭───────────────╮
+/// │ Healthy: true │
+/// ╰───────────────╯");
+/// ```
+///
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// # use plain_msgbox::*;
+/// #[derive(MsgBox)]
+/// struct Config {
+///     optimization_level: u8,
+///     #[msgbox(rename = "Caching enabled")]
+///     caching: bool,
+/// }
+///
+/// let config = Config { optimization_level: 1, caching: true };
+/// assert_eq!(config.to_msgbox(TextBoxConfig::default().with_caption("Config")), "\
+/// ╭──────────────────────────╮
+/// │ Optimization level: 1    │
+/// │ Caching enabled:    true │
+/// <Config>───────────────────╯");
+/// # }
+/// ```
+pub trait MsgBox {
+    /// The entries displaying the value, nested at the given level.
+    fn entries(&self, level: usize) -> Vec<KeyValue<'static>>;
+
+    /// Generate a message box displaying the entries of the value, see [`generate_key_values`].
+    fn to_msgbox(&self, config: TextBoxConfig<'_>) -> String {
+        generate_key_values(&self.entries(0), config)
+    }
+}
+
+/// Create the top-level entries of the given pairs, with the keys padded to a common width
+/// and followed by the separator, if any, so that the separators line up as well.
+pub(crate) fn pairs<K: fmt::Display, V: fmt::Display>(
+    pairs: &[(K, V)],
+    separator: Option<&str>,
+    measure: Measure<'_>,
+) -> Vec<KeyValue<'static>> {
+    let keys = pairs
+        .iter()
+        .map(|(key, _)| key.to_string())
+        .collect::<Vec<_>>();
+    let key_width = keys.iter().map(|key| measure.width(key)).max().unwrap_or(0);
+    let separator = separator.map(str::trim_end).unwrap_or_default();
+
+    keys.into_iter()
+        .zip(pairs)
+        .map(|(mut key, (_, value))| {
+            if !separator.is_empty() {
+                let padding = key_width - measure.width(&key);
+                push_spaces(&mut key, padding);
+                key.push_str(separator);
+            }
+            KeyValue::new(key, value.to_string())
+        })
+        .collect()
+}
+
+/// Lay out the entries as lines: the keys are indented by their levels, and the values
+/// start at the same column, one space after the widest indented key with a value.
+/// The values that don't fit into `max_width` columns, if given, are wrapped into
+/// the continuation lines starting at the value column.
+pub(crate) fn layout(
+    entries: &[KeyValue<'_>],
+    measure: Measure<'_>,
+    max_width: Option<usize>,
+) -> Vec<String> {
+    let key_width = |entry: &KeyValue<'_>| entry.level * INDENT_WIDTH + measure.width(&entry.key);
+    let column = entries
+        .iter()
+        .filter(|entry| entry.value.is_some())
+        .map(key_width)
+        .max()
+        .unwrap_or(0);
+
+    let value_width = max_width.map(|width| width.saturating_sub(column + 1));
+
+    let mut lines = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let mut line = String::new();
+        push_spaces(&mut line, entry.level * INDENT_WIDTH);
+        line.push_str(&entry.key);
+
+        let value = match &entry.value {
+            Some(value) => value,
+            None => {
+                lines.push(line);
+                continue;
+            }
+        };
+        push_spaces(&mut line, column - key_width(entry) + 1);
+        match value_width.filter(|&width| measure.width(value) > width) {
+            Some(width) => {
+                for (index, range) in wrap(value, width, measure).into_iter().enumerate() {
+                    if index > 0 {
+                        lines.push(std::mem::take(&mut line));
+                        push_spaces(&mut line, column + 1);
+                    }
+                    line.push_str(&value[range]);
+                }
+            }
+            None => line.push_str(value),
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ByteWidth;
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn test_derive() {
+        use crate::MsgBox;
+
+        #[derive(MsgBox)]
+        struct Optimizations {
+            constant_folding: bool,
+            #[msgbox(rename = "Peephole Optimizations")]
+            peephole: bool,
+        }
+
+        #[derive(MsgBox)]
+        struct Config<'a> {
+            call_stack_size: usize,
+            #[msgbox(nested)]
+            optimizations: Optimizations,
+            #[msgbox(skip)]
+            #[allow(dead_code)]
+            name: &'a str,
+        }
+
+        let config = Config {
+            call_stack_size: 1024,
+            optimizations: Optimizations {
+                constant_folding: true,
+                peephole: false,
+            },
+            name: "skipped",
+        };
+        assert_eq!(
+            layout(&config.entries(0), &ByteWidth, None),
+            [
+                "Call stack size:          1024",
+                "Optimizations:",
+                "  Constant folding:       true",
+                "  Peephole Optimizations: false",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pairs() {
+        let entries = [("cfg_export", "false"), ("caching", "true")];
+        let lines = |separator| layout(&pairs(&entries, separator, &ByteWidth), &ByteWidth, None);
+        assert_eq!(lines(None), ["cfg_export false", "caching    true"]);
+        assert_eq!(
+            lines(Some(" = ")),
+            ["cfg_export = false", "caching    = true"]
+        );
+        assert_eq!(lines(Some(":")), ["cfg_export: false", "caching   : true"]);
+        assert!(pairs::<&str, &str>(&[], None, &ByteWidth).is_empty());
+    }
+
+    #[test]
+    fn test_layout() {
+        let entries = [
+            KeyValue::new("a:", "1"),
+            KeyValue::header("nested:"),
+            KeyValue::new("long key:", "2").with_level(1),
+            KeyValue::header("deeper:").with_level(1),
+            KeyValue::new("b:", "3").with_level(2),
+        ];
+
+        assert_eq!(
+            layout(&entries, &ByteWidth, None),
+            [
+                "a:          1",
+                "nested:",
+                "  long key: 2",
+                "  deeper:",
+                "    b:      3",
+            ]
+        );
+        assert!(layout(&[], &ByteWidth, None).is_empty());
+
+        let entries = [
+            KeyValue::new("path:", "/usr/local/bin"),
+            KeyValue::new("shell:", "zsh"),
+        ];
+        assert_eq!(
+            layout(&entries, &ByteWidth, Some(12)),
+            ["path:  /usr/", "       local", "       /bin", "shell: zsh"]
+        );
+        let entries = [KeyValue::new("k:", "a long value")];
+        assert_eq!(
+            layout(&entries, &ByteWidth, Some(9)),
+            ["k: a long", "   value"]
+        );
+    }
+}
@@ -40,6 +40,119 @@
 //! │ A string: abcdefghi │
 //! ╰─────────────────────╯");
 //! ```
+//!
+//! # Features
+//! - `rayon`: measure and format boxes with a huge number of lines in parallel.
+//! - `colored`: convert the strings styled by `colored` into a [`StyledLine`].
+//! - `crossterm`: print the boxes at terminal coordinates with [`print_box_at`].
+//! - `derive`: implement [`MsgBox`] for the structs with `#[derive(MsgBox)]`.
+//! - `ffi`: expose the C interface declared in `include/plain_msgbox.h`.
+//! - `indicatif`: print the boxes above the progress bars with [`println_box`] and [`multi_println_box`].
+//! - `memchr`: find the escape sequences in the long lines with the vectorized `memchr` search.
+//! - `owo-colors`: convert the values styled by `owo-colors` into a [`StyledLine`].
+//! - `python`: expose `generate_box`, `generate_split`, and `generate_flow` as a Python module with PyO3.
+//! - `ratatui`: render the boxes in the terminal user interfaces with [`MsgBoxWidget`].
+//! - `regex`: highlight the matches of regular expressions in the content lines with [`RegexHighlighter`].
+//! - `syntect`: frame the code snippets highlighted by `syntect` with [`box_code`].
+//! - `termcolor`: write the colored boxes into a `termcolor::WriteColor` with [`write_colored`].
+//! - `unicode-width`: measure the text as the terminals do with [`UnicodeWidth`].
+//! - `wasm`: expose `generateBox` and `BoxOptions` to JavaScript with `wasm-bindgen`.
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+};
+
+// Lets the code generated by `#[derive(MsgBox)]` refer to this crate from its own tests.
+#[cfg(all(test, feature = "derive"))]
+extern crate self as plain_msgbox;
+
+mod ansi;
+mod backtrace;
+mod border;
+mod builder;
+mod charset;
+#[cfg(feature = "syntect")]
+mod code;
+#[cfg(feature = "termcolor")]
+mod colored;
+mod command;
+#[cfg(feature = "crossterm")]
+mod cursor;
+mod document;
+mod fallback;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod flow;
+mod grapheme;
+mod help;
+mod highlight;
+#[cfg(any(feature = "colored", feature = "owo-colors"))]
+mod interop;
+mod json;
+mod kv;
+mod line;
+mod markup;
+mod measure;
+mod partial;
+mod parts;
+mod plan;
+#[cfg(feature = "indicatif")]
+mod progress;
+#[cfg(feature = "python")]
+mod python;
+mod renderer;
+mod section;
+mod source;
+mod spacer;
+mod stream;
+mod style;
+mod table;
+mod template;
+#[cfg(feature = "ratatui")]
+mod tui;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+mod wrap;
+
+pub use ansi::{hyperlink, truncate_styled, wrap_styled};
+pub use backtrace::box_backtrace;
+pub use border::{Border, BorderRow, BorderSegments};
+pub use builder::BoxBuilder;
+pub use charset::Charset;
+#[cfg(feature = "syntect")]
+pub use code::box_code;
+#[cfg(feature = "termcolor")]
+pub use colored::write_colored;
+pub use command::box_command_output;
+#[cfg(feature = "crossterm")]
+pub use cursor::{print_box_at, write_box_at};
+pub use document::BoxDocument;
+pub use fallback::{is_ascii_safe, to_ascii_fallback};
+pub use help::{generate_help, generate_help_sections};
+pub use highlight::Highlighter;
+#[cfg(feature = "regex")]
+pub use highlight::RegexHighlighter;
+pub use kv::{KeyValue, MsgBox};
+pub use line::Line;
+#[cfg(feature = "unicode-width")]
+pub use measure::UnicodeWidth;
+pub use measure::{truncate_visible, AnsiWidth, ByteWidth, CharWidth, WidthMeasurer};
+pub use partial::PartialConfig;
+pub use parts::{bottom_border, render_line, top_border, BoxPart, BoxParts};
+#[cfg(feature = "derive")]
+pub use plain_msgbox_derive::MsgBox;
+pub use plan::RenderPlan;
+#[cfg(feature = "indicatif")]
+pub use progress::{multi_println_box, println_box};
+pub use renderer::{Renderer, Row};
+pub use section::Section;
+pub use source::{IterSource, LineSource};
+pub use stream::BoxStream;
+pub use style::{Color, LineStyle, Span, Style, StyledLine, RESET};
+pub use table::{generate_table, TableConfig};
+pub use template::BoxTemplate;
+#[cfg(feature = "ratatui")]
+pub use tui::MsgBoxWidget;
 
 /// Generate a new message box using the provided lines.
 ///
@@ -91,6 +204,153 @@ pub fn generate_with_caption(lines: &[String], last_line_caption: &str) -> Strin
     )
 }
 
+/// Generate a new message box displaying the lines of the given text.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// let msg = generate_box_str("Saved 3 files\nSkipped 1 file\n");
+///
+/// assert_eq!(msg, "\
+/// ╭────────────────╮
+/// │ Saved 3 files  │
+/// │ Skipped 1 file │
+/// ╰────────────────╯");
+/// ```
+#[inline]
+pub fn generate_box_str(text: &str) -> String {
+    generate_with_config_str(text, Default::default())
+}
+
+/// Generate a new message box displaying the lines of the given text according to the given
+/// config, like a banner embedded with `include_str!`. Only a single trailing line ending
+/// is dropped, so the blank lines at the end of the text are kept unless the config
+/// [trims](TextBoxConfig::trim_blank_lines) them.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// let banner = "  _\n |_)\n |_)\n\n";
+/// let config = TextBoxConfig::ascii();
+///
+/// assert_eq!(generate_with_config_str(banner, config), "\
+/// +------+
+/// |   _  |
+/// |  |_) |
+/// |  |_) |
+/// |      |
+/// +------+");
+/// assert_eq!(generate_with_config_str(banner, config.trim_blank_lines()), "\
+/// +------+
+/// |   _  |
+/// |  |_) |
+/// |  |_) |
+/// +------+");
+/// ```
+#[inline]
+pub fn generate_with_config_str(text: &str, config: TextBoxConfig<'_>) -> String {
+    generate_with_config(&split_lines(text), config)
+}
+
+/// Generate a new message box displaying the lines of the given text, like a banner embedded
+/// with `include_str!`. The trailing line ending is dropped, see [`generate_with_config_str`].
+///
+/// ```
+/// # use plain_msgbox::*;
+/// let msg = generate_from_str("Build passed\nin 3.2s\n");
+///
+/// assert_eq!(msg, "\
+/// ╭──────────────╮
+/// │ Build passed │
+/// │ in 3.2s      │
+/// ╰──────────────╯");
+/// ```
+#[inline]
+pub fn generate_from_str(text: &str) -> String {
+    generate_box(&split_lines(text))
+}
+
+/// Generate a new message box displaying the lines of the given text,
+/// with the given caption on the last line.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// let msg = generate_with_caption_str("Saved 3 files\nSkipped 1 file", "Sync");
+///
+/// assert_eq!(msg, "\
+/// ╭────────────────╮
+/// │ Saved 3 files  │
+/// │ Skipped 1 file │
+/// <Sync>───────────╯");
+/// ```
+#[inline]
+pub fn generate_with_caption_str(text: &str, last_line_caption: &str) -> String {
+    generate_with_caption(&split_lines(text), last_line_caption)
+}
+
+/// Generate a new message box displaying the value pretty-printed with `{:#?}`.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// #[derive(Debug)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// assert_eq!(boxed_debug(&Point { x: 1, y: -2 }), "\
+/// ╭────────────╮
+/// │ Point {    │
+/// │     x: 1,  │
+/// │     y: -2, │
+/// │ }          │
+/// ╰────────────╯");
+/// ```
+pub fn boxed_debug<T: fmt::Debug + ?Sized>(value: &T) -> String {
+    generate_box_str(&format!("{:#?}", value))
+}
+
+/// Generate a new message box displaying the lines of the value formatted with `Display`.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// use std::path::Path;
+///
+/// let error = std::io::Error::new(std::io::ErrorKind::NotFound, "config.toml not found");
+/// assert_eq!(boxed(&error), "\
+/// ╭───────────────────────╮
+/// │ config.toml not found │
+/// ╰───────────────────────╯");
+/// assert_eq!(boxed(&Path::new("/etc/hosts").display()), "\
+/// ╭────────────╮
+/// │ /etc/hosts │
+/// ╰────────────╯");
+/// ```
+pub fn boxed<T: fmt::Display + ?Sized>(value: &T) -> String {
+    boxed_with_config(value, Default::default())
+}
+
+/// Generate a new message box displaying the lines of the value formatted with `Display`,
+/// according to the given config.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// let msg = boxed_with_config(&"disk full\nretrying", TextBoxConfig::ascii().with_caption("Error"));
+///
+/// assert_eq!(msg, "\
+/// +-----------+
+/// | disk full |
+/// | retrying  |
+/// <Error>-----+");
+/// ```
+pub fn boxed_with_config<T: fmt::Display + ?Sized>(value: &T, config: TextBoxConfig<'_>) -> String {
+    generate_with_config(&split_lines(&value.to_string()), config)
+}
+
+/// Split the text into lines, accepting both `\n` and `\r\n`. A trailing line ending
+/// doesn't produce an empty line.
+fn split_lines(text: &str) -> Vec<String> {
+    text.lines().map(String::from).collect()
+}
+
 /// Generate a new message box according the given config.
 ///
 /// ```
@@ -106,52 +366,416 @@ pub fn generate_with_caption(lines: &[String], last_line_caption: &str) -> Strin
 /// ║ Address: 0x55e7d53f0860             ║
 /// <Fn Info>═════════════════════════════╝");
 /// ```
+#[inline]
 pub fn generate_with_config(lines: &[String], config: TextBoxConfig<'_>) -> String {
-    let longest_line = lines.iter().map(|r| r.len()).max().unwrap_or(0);
+    Renderer::new(config).render(lines)
+}
 
-    let longest_line = config
-        .last_line_caption
-        .map(str::len)
-        .unwrap_or(0)
-        .max(longest_line);
+/// Write a new message box generated according to the given config into the sink, like the
+/// formatter of a `Display` implementation, see [`Renderer::render_into`].
+///
+/// ```
+/// # use plain_msgbox::*;
+/// use std::fmt;
+///
+/// struct Report {
+///     passed: usize,
+///     failed: usize,
+/// }
+///
+/// impl fmt::Display for Report {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         let lines = [format!("passed: {}", self.passed), format!("failed: {}", self.failed)];
+///         generate_into(f, &lines, TextBoxConfig::default().with_caption("Tests"))
+///     }
+/// }
+///
+/// assert_eq!(Report { passed: 12, failed: 0 }.to_string(), "\
+/// ╭────────────╮
+/// │ passed: 12 │
+/// │ failed: 0  │
+/// <Tests>──────╯");
+/// ```
+#[inline]
+pub fn generate_into<W: fmt::Write + ?Sized>(
+    out: &mut W,
+    lines: &[String],
+    config: TextBoxConfig<'_>,
+) -> fmt::Result {
+    Renderer::new(config).render_into(out, lines)
+}
 
-    let mut result = vec![
-        config.left_top_corner.to_owned()
-            + &config.horizontal_bar.repeat(longest_line + 2)
-            + config.right_top_corner,
-    ];
-    result.reserve(lines.len() + 1);
+/// Generate a new message box according to the borrowed config, so a single long-lived config
+/// can be shared by many calls and threads.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// static CONFIG: TextBoxConfig<'static> = TextBoxConfig::dos().with_caption("Worker");
+///
+/// let handles = (0..2)
+///     .map(|n| std::thread::spawn(move || generate_with_config_ref(&[format!("Job {}", n)], &CONFIG)))
+///     .collect::<Vec<_>>();
+/// let boxes = handles.into_iter().map(|handle| handle.join().unwrap()).collect::<Vec<_>>();
+///
+/// assert_eq!(boxes[1], "\
+/// ╔════════╗
+/// ║ Job 1  ║
+/// <Worker>═╝");
+/// ```
+#[inline]
+pub fn generate_with_config_ref(lines: &[String], config: &TextBoxConfig<'_>) -> String {
+    Renderer::new(*config).render(lines)
+}
 
-    for line in lines {
-        let spaces = " ".repeat(longest_line - line.len());
-        result.push(format!(
-            "{} {}{} {}",
-            config.vertical_bar, line, spaces, config.vertical_bar
-        ));
-    }
+/// Generate a new message box with the default config from any lines of text, like the
+/// `&str` slices or an iterator, without allocating a `String` for every line upfront.
+/// [`generate_box`] keeps taking `&[String]`, so that `generate_box(&[])` still compiles.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// let msg = generate_box_iter("alpha beta".split(' '));
+///
+/// assert_eq!(msg, "\
+/// ╭───────╮
+/// │ alpha │
+/// │ beta  │
+/// ╰───────╯");
+/// assert_eq!(generate_box_iter(["a", "b"]), generate_box(&[String::from("a"), String::from("b")]));
+/// ```
+pub fn generate_box_iter<I>(lines: I) -> String
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    generate_with_config_iter(lines, Default::default())
+}
 
-    if let Some(caption) = config.last_line_caption {
-        result.push(format!(
-            "<{}>{}{}",
-            caption,
-            config
-                .horizontal_bar
-                .repeat(longest_line - caption.len() + 1),
-            config.right_bottom_corner
-        ));
-    } else {
-        result.push(
-            config.left_bottom_corner.to_owned()
-                + &config.horizontal_bar.repeat(longest_line + 2)
-                + config.right_bottom_corner,
-        );
+/// Generate a new message box according to the given config from any lines of text,
+/// see [`generate_box_iter`].
+///
+/// ```
+/// # use plain_msgbox::*;
+/// let names = vec!["serde", "rayon"];
+/// let msg = generate_with_config_iter(&names, TextBoxConfig::ascii().with_caption("deps"));
+///
+/// assert_eq!(msg, "\
+/// +-------+
+/// | serde |
+/// | rayon |
+/// <deps>--+");
+/// ```
+pub fn generate_with_config_iter<I>(lines: I, config: TextBoxConfig<'_>) -> String
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    let lines = lines
+        .into_iter()
+        .map(|line| line.as_ref().to_string())
+        .collect::<Vec<_>>();
+    generate_with_config(&lines, config)
+}
+
+/// Generate a message box for every set of lines, making all of the boxes as wide as the widest one.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// let boxes = generate_group(&[
+///     &[String::from("Name: plain-msgbox")],
+///     &[String::from("Version: 0.1.0")],
+/// ], TextBoxConfig::default());
+///
+/// assert_eq!(boxes.join("\n"), "\
+/// ╭────────────────────╮
+/// │ Name: plain-msgbox │
+/// ╰────────────────────╯
+/// ╭────────────────────╮
+/// │ Version: 0.1.0     │
+/// ╰────────────────────╯");
+/// ```
+pub fn generate_group(boxes: &[&[String]], config: TextBoxConfig<'_>) -> Vec<String> {
+    let renderer = Renderer::new(config);
+    let width = boxes
+        .iter()
+        .map(|lines| renderer.measure(lines))
+        .max()
+        .unwrap_or(0);
+
+    let renderer = Renderer::with_width(*renderer.config(), width);
+    boxes.iter().map(|lines| renderer.render(lines)).collect()
+}
+
+/// Wrap the given already rendered message boxes into an outer box, stacking them vertically.
+/// Every character of the inner boxes is assumed to take a single column.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// let inner = [
+///     generate_with_caption(&[String::from("cpu: 12%")], "Host A"),
+///     generate_with_caption(&[String::from("cpu: 97%")], "Host B"),
+/// ];
+/// let msg = generate_frame(&inner, TextBoxConfig::dos().with_caption("Cluster"));
+///
+/// assert_eq!(msg, "\
+/// ╔══════════════╗
+/// ║ ╭──────────╮ ║
+/// ║ │ cpu: 12% │ ║
+/// ║ <Host A>───╯ ║
+/// ║ ╭──────────╮ ║
+/// ║ │ cpu: 97% │ ║
+/// ║ <Host B>───╯ ║
+/// <Cluster>══════╝");
+/// ```
+pub fn generate_frame(boxes: &[String], config: TextBoxConfig<'_>) -> String {
+    let lines = boxes
+        .iter()
+        .flat_map(|inner| inner.lines().map(String::from))
+        .collect::<Vec<_>>();
+    let config = TextBoxConfig {
+        measurer: Some(config.measurer.unwrap_or(&CharWidth)),
+        ..config
+    };
+    Renderer::new(config).render(&lines)
+}
+
+/// Generate a message box displaying two sets of lines side by side, separated by
+/// the [`column_divider`](TextBoxConfig::column_divider) of the config.
+/// The shorter column is padded with empty lines.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// let request = [String::from("GET /health"), String::from("Host: db-1")];
+/// let response = [String::from("200 OK")];
+/// let msg = generate_split(&request, &response, TextBoxConfig::default());
+///
+/// assert_eq!(msg, "\
+/// ╭──────────────────────╮
+/// │ GET /health │ 200 OK │
+/// │ Host: db-1  │        │
+/// ╰──────────────────────╯");
+/// ```
+pub fn generate_split(left: &[String], right: &[String], config: TextBoxConfig<'_>) -> String {
+    Renderer::new(config).render_split(left, right)
+}
+
+/// Generate a message box that flows the given short items into as many columns as fit
+/// into a box that is at most `width` columns wide, borders included. The columns are filled
+/// from top to bottom like `ls` does.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// let files = ["Cargo.toml", "README.md", "src", "target", "tests"]
+///     .iter()
+///     .map(|s| s.to_string())
+///     .collect::<Vec<_>>();
+/// let msg = generate_flow(&files, 30, TextBoxConfig::default());
+///
+/// assert_eq!(msg, "\
+/// ╭───────────────────────────╮
+/// │ Cargo.toml  src     tests │
+/// │ README.md   target        │
+/// ╰───────────────────────────╯");
+/// ```
+pub fn generate_flow(items: &[String], width: usize, config: TextBoxConfig<'_>) -> String {
+    let items = items.iter().map(String::as_str).collect::<Vec<_>>();
+    let lines = flow::flow(
+        &items,
+        width.saturating_sub(renderer::BORDER_WIDTH),
+        config.measure(),
+        config.align_numbers,
+    );
+    Renderer::new(config).render(&lines)
+}
+
+/// Generate a message box displaying the given key-value entries. The keys are indented
+/// by their nesting levels and the values line up in a common column, see [`KeyValue`]
+/// and [`Renderer::render_key_values`].
+///
+/// ```
+/// # use plain_msgbox::*;
+/// let entries = [
+///     KeyValue::new("Optimization level:", "1"),
+///     KeyValue::header("Optimizations:"),
+///     KeyValue::new("Constant Folding:", "true").with_level(1),
+///     KeyValue::new("Peephole Optimizations:", "true").with_level(1),
+/// ];
+/// let msg = generate_key_values(&entries, TextBoxConfig::default().with_caption("Config"));
+///
+/// assert_eq!(msg, "\
+/// ╭────────────────────────────────╮
+/// │ Optimization level:       1    │
+/// │ Optimizations:                 │
+/// │   Constant Folding:       true │
+/// │   Peephole Optimizations: true │
+/// <Config>─────────────────────────╯");
+/// ```
+pub fn generate_key_values(entries: &[KeyValue<'_>], config: TextBoxConfig<'_>) -> String {
+    Renderer::new(config).render_key_values(entries)
+}
+
+/// Generate a message box displaying the given key-value pairs with the keys padded to
+/// a common column. The separator, if given, is inserted between the padded keys and
+/// the values, and a space is inserted otherwise, see [`generate_key_values`].
+///
+/// ```
+/// # use plain_msgbox::*;
+/// let pairs = [("(misc): cfg_export", false), ("(misc): caching", true)];
+/// let msg = generate_kv_box(&pairs, Some(" = "), TextBoxConfig::default().with_caption("Config"));
+///
+/// assert_eq!(msg, "\
+/// ╭────────────────────────────╮
+/// │ (misc): cfg_export = false │
+/// │ (misc): caching    = true  │
+/// <Config>─────────────────────╯");
+///
+/// let pairs = [("Call stack size:", 1024), ("Optimization level:", 1)];
+/// assert_eq!(generate_kv_box(&pairs, None, TextBoxConfig::default()), "\
+/// ╭──────────────────────────╮
+/// │ Call stack size:    1024 │
+/// │ Optimization level: 1    │
+/// ╰──────────────────────────╯");
+/// ```
+pub fn generate_kv_box<K: fmt::Display, V: fmt::Display>(
+    pairs: &[(K, V)],
+    separator: Option<&str>,
+    config: TextBoxConfig<'_>,
+) -> String {
+    let entries = kv::pairs(pairs, separator, config.measure());
+    Renderer::new(config).render_key_values(&entries)
+}
+
+/// Generate a message box displaying the entries of the map in the order of their keys,
+/// see [`generate_kv_box`].
+///
+/// ```
+/// # use plain_msgbox::*;
+/// use std::collections::BTreeMap;
+///
+/// let map = BTreeMap::from([("threads", "8"), ("mode", "release")]);
+/// assert_eq!(generate_btree_map(&map, Some(": "), TextBoxConfig::default()), "\
+/// ╭──────────────────╮
+/// │ mode   : release │
+/// │ threads: 8       │
+/// ╰──────────────────╯");
+/// ```
+pub fn generate_btree_map<K: fmt::Display, V: fmt::Display>(
+    map: &BTreeMap<K, V>,
+    separator: Option<&str>,
+    config: TextBoxConfig<'_>,
+) -> String {
+    let pairs = map.iter().collect::<Vec<_>>();
+    generate_kv_box(&pairs, separator, config)
+}
+
+/// Generate a message box displaying the entries of the map, sorted by their displayed keys
+/// if `sorted` is set, or in the iteration order of the map otherwise. See [`generate_kv_box`].
+///
+/// ```
+/// # use plain_msgbox::*;
+/// use std::collections::HashMap;
+///
+/// let map = HashMap::from([("RUST_LOG", "debug"), ("HOME", "/root")]);
+/// assert_eq!(generate_hash_map(&map, true, Some(" = "), TextBoxConfig::default()), "\
+/// ╭──────────────────╮
+/// │ HOME     = /root │
+/// │ RUST_LOG = debug │
+/// ╰──────────────────╯");
+/// ```
+pub fn generate_hash_map<K: fmt::Display, V: fmt::Display, S>(
+    map: &HashMap<K, V, S>,
+    sorted: bool,
+    separator: Option<&str>,
+    config: TextBoxConfig<'_>,
+) -> String {
+    let mut pairs = map.iter().collect::<Vec<_>>();
+    if sorted {
+        pairs.sort_by_cached_key(|(key, _)| key.to_string());
     }
+    generate_kv_box(&pairs, separator, config)
+}
 
-    result.join("\n")
+/// Generate a message box displaying the given groups of lines, with a horizontal rule
+/// between every two groups. The empty groups are skipped.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// let usage = [String::from("Usage: app [OPTIONS]")];
+/// let options = [String::from("-v  Verbose"), String::from("-h  Help")];
+/// let msg = generate_separated(&[&usage, &options], TextBoxConfig::default());
+///
+/// assert_eq!(msg, "\
+/// ╭──────────────────────╮
+/// │ Usage: app [OPTIONS] │
+/// ├──────────────────────┤
+/// │ -v  Verbose          │
+/// │ -h  Help             │
+/// ╰──────────────────────╯");
+/// ```
+pub fn generate_separated(groups: &[&[String]], config: TextBoxConfig<'_>) -> String {
+    Renderer::new(config).render_separated(groups)
 }
 
+/// Generate a message box displaying the given sections, with a horizontal rule between every
+/// two sections. The collapsed sections only display their heading with the number of the
+/// hidden lines, unless the config [expands](TextBoxConfig::expand_sections) them.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// let build = vec![String::from("Compiling app")];
+/// let tests = (1..=37).map(|n| format!("test {} ok", n)).collect::<Vec<_>>();
+/// let sections = [Section::new("Build", build), Section::new("Tests", tests).collapsed()];
+///
+/// let config = TextBoxConfig::default().with_measurer(&CharWidth);
+///
+/// assert_eq!(generate_sections(&sections, config), "\
+/// ╭───────────────────────────╮
+/// │ ▾ Build                   │
+/// │ Compiling app             │
+/// ├───────────────────────────┤
+/// │ ▸ Tests (37 lines hidden) │
+/// ╰───────────────────────────╯");
+///
+/// let expanded = config.expand_sections();
+/// assert_eq!(generate_sections(&sections, expanded).lines().count(), 43);
+/// ```
+pub fn generate_sections(sections: &[Section<'_>], config: TextBoxConfig<'_>) -> String {
+    Renderer::new(config).render_sections(sections)
+}
+
+/// Generate a message box displaying the given lines, each with its own alignment, indent
+/// and style, see [`Line`].
+///
+/// ```
+/// # use plain_msgbox::*;
+/// let lines = [
+///     Line::new("Benchmarks").with_alignment(Alignment::Center),
+///     Line::new("parse: 1.25 ms").with_alignment(Alignment::Right),
+///     Line::new("lex: 0.4 ms").with_alignment(Alignment::Right),
+///     Line::new("total: 1.63 ms").with_alignment(Alignment::Right),
+/// ];
+///
+/// assert_eq!(generate_lines(&lines, TextBoxConfig::default().with_min_width(20)), "\
+/// ╭──────────────────╮
+/// │    Benchmarks    │
+/// │   parse: 1.25 ms │
+/// │      lex: 0.4 ms │
+/// │   total: 1.63 ms │
+/// ╰──────────────────╯");
+/// ```
+pub fn generate_lines(lines: &[Line<'_>], config: TextBoxConfig<'_>) -> String {
+    Renderer::new(config).render_lines(lines)
+}
+
+/// A hook returning the [`LineStyle`] of the input line with the given index and text.
+pub type LineStyleHook<'a> = dyn Fn(usize, &str) -> LineStyle + Sync + 'a;
+
+/// A hook transforming the finished output row with the given index and kind.
+pub type PostRenderHook<'a> = dyn Fn(usize, RowKind, String) -> String + Sync + 'a;
+
 /// Configure the last line caption and the box drawing characters.
-#[derive(Debug, Clone)]
+///
+/// The config only holds references, so it's cheap to copy, and it can be shared by the threads.
+#[derive(Clone, Copy)]
 pub struct TextBoxConfig<'a> {
     /// The character to use as the horizontal bar.
     pub horizontal_bar: &'a str,
@@ -165,77 +789,1360 @@ pub struct TextBoxConfig<'a> {
     pub right_top_corner: &'a str,
     /// The character to use for the right bottom corner.
     pub right_bottom_corner: &'a str,
+    /// The character to use where a horizontal rule meets the left border.
+    pub left_tee: &'a str,
+    /// The character to use where a horizontal rule meets the right border.
+    pub right_tee: &'a str,
+    /// The glyph drawn below the box when it's rendered as a speech bubble.
+    pub tail: &'a str,
     /// The caption displayed on the last line of the box.
     pub last_line_caption: Option<&'a str>,
+    /// Produces a preformatted timestamp that is displayed right-aligned on the first row of the box.
+    /// The provider is invoked once per render.
+    pub timestamp: Option<&'a (dyn Fn() -> String + Sync)>,
+    /// Produces the fields displayed on the last content row of the box, separated from the body by a rule.
+    /// The provider is invoked once per render.
+    pub footer: Option<&'a (dyn Fn() -> Vec<String> + Sync)>,
+    /// Render the box as a speech bubble with the [`tail`](Self::tail) starting at this column below the box.
+    pub tail_column: Option<usize>,
+    /// Mirror the layout for right-to-left output: the content is right-aligned
+    /// and the caption is attached to the right bottom corner.
+    pub mirrored: bool,
+    /// A short caption rendered vertically, one character per content row, in place of the side border.
+    pub side_caption: Option<&'a str>,
+    /// The border that displays the [`side_caption`](Self::side_caption).
+    pub side_caption_border: Side,
+    /// Whether each line is rendered as a row or as a column of the box.
+    pub orientation: Orientation,
+    /// Consulted with the index and the text of every input line to decide how it's displayed.
+    pub line_style: Option<&'a LineStyleHook<'a>>,
+    /// Invoked with every finished output row before it's added to the box.
+    pub post_render: Option<&'a PostRenderHook<'a>>,
+    /// Parse every input line as the inline markup, see [`StyledLine::from_markup`].
+    pub markup: bool,
+    /// Omit the right border and the trailing padding of the rows.
+    pub ragged: bool,
+    /// Remove the trailing spaces and tabs of every input line before measuring it.
+    pub trim_trailing_whitespace: bool,
+    /// Replace every run of consecutive blank input lines with a single blank row.
+    pub collapse_blank_lines: bool,
+    /// Remove the blank input lines at the start and at the end of the box.
+    pub trim_blank_lines: bool,
+    /// The number of blank rows inserted between the rows of the input lines.
+    pub line_spacing: usize,
+    /// The character separating the columns of [`generate_split`].
+    pub column_divider: &'a str,
+    /// Right-align the numeric values within the columns of the columnar layouts.
+    pub align_numbers: bool,
+    /// The number of spaces inserted before every output row.
+    pub indent: usize,
+    /// Attached before every output row, after the [`indent`](Self::indent).
+    pub row_prefix: &'a str,
+    /// Attached after every output row.
+    pub row_suffix: &'a str,
+    /// Display the content without the terminal styles of the line style hook, the markup, and the styled lines.
+    pub strip_styles: bool,
+    /// Draws the border of the box instead of the box drawing characters above.
+    pub border: Option<&'a (dyn Border + Sync)>,
+    /// Measures the width of the text instead of counting its bytes.
+    pub measurer: Option<&'a (dyn WidthMeasurer + Sync)>,
+    /// What happens to the lines that don't fit into a box with a fixed width.
+    /// See [`TextBoxConfig::with_ellipsis`] for marking the truncated lines.
+    pub overflow: OverflowPolicy,
+    /// Truncate the caption to the width of the content instead of widening the box to fit it.
+    pub truncate_caption: bool,
+    /// The pattern repeated in the padding of the content rows instead of the spaces.
+    pub padding_fill: Option<&'a str>,
+    /// The set of characters the glyphs are drawn with, applied when the box is rendered.
+    pub charset: Charset,
+    /// Display the lines of the collapsed [`Section`]s as if they were expanded.
+    pub expand_sections: bool,
+    /// Skip the frames of the runtime, the panic machinery and the standard library, see [`box_backtrace`].
+    pub trim_internal_frames: bool,
+    /// Replace every run of consecutive identical input lines with its first line suffixed with the length of the run.
+    pub dedupe_lines: bool,
+    /// Prefix every line of the code boxes with its number.
+    pub line_numbers: bool,
+    /// Finds the parts of the content lines to highlight.
+    pub highlighter: Option<&'a (dyn Highlighter + Sync)>,
+    /// The widest the boxes fitted to their content can get, borders included.
+    pub max_width: Option<usize>,
+    /// Replaces the end of the lines that are truncated to fit into the box.
+    pub ellipsis: Option<&'a str>,
+    /// The exact width of every box, borders included, see [`Renderer::with_width`].
+    pub width: Option<usize>,
+    /// The minimum width of the boxes fitted to their content, borders included.
+    pub min_width: Option<usize>,
+    /// The alignment of the content lines that don't override it.
+    pub alignment: Alignment,
+    /// The number of blank rows after the top border and before the bottom border.
+    pub vertical_padding: usize,
+    /// The position of the caption within the bottom border.
+    pub caption_alignment: Alignment,
+    /// The number of columns between the tab stops the tabs of the lines are expanded to,
+    /// or `None` to keep the tabs as is.
+    pub tab_width: Option<usize>,
+    /// What happens to the control characters of the lines.
+    pub control_chars: ControlPolicy,
+    /// The line ending placed between the output rows.
+    pub line_ending: &'a str,
+    /// Whether the output ends with a line ending.
+    pub trailing_newline: bool,
 }
 
-impl<'a> TextBoxConfig<'a> {
-    /// Create a DOS-styled text box config.
-    pub fn dos() -> Self {
+impl<'a> TextBoxConfig<'a> {
+    /// Create the default text box config. Like the other constructors and the builder methods,
+    /// it can be used to define the configs as constants.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// const ERROR: TextBoxConfig<'static> = TextBoxConfig::new().with_caption("Error").with_indent(2);
+    ///
+    /// assert_eq!(generate_with_config(&[String::from("Not found")], ERROR), "  \
+    /// ╭───────────╮
+    ///   │ Not found │
+    ///   <Error>─────╯");
+    /// ```
+    pub const fn new() -> Self {
+        Self {
+            horizontal_bar: default::DEFAULT_HORIZONTAL_BAR,
+            vertical_bar: default::DEFAULT_VERTICAL_BAR,
+            left_top_corner: default::DEFAULT_LEFT_TOP_CORNER,
+            left_bottom_corner: default::DEFAULT_LEFT_BOTTOM_CORNER,
+            right_top_corner: default::DEFAULT_RIGHT_TOP_CORNER,
+            right_bottom_corner: default::DEFAULT_RIGHT_BOTTOM_CORNER,
+            left_tee: default::DEFAULT_LEFT_TEE,
+            right_tee: default::DEFAULT_RIGHT_TEE,
+            tail: default::DEFAULT_TAIL,
+            last_line_caption: None,
+            timestamp: None,
+            footer: None,
+            tail_column: None,
+            mirrored: false,
+            side_caption: None,
+            side_caption_border: Side::Left,
+            orientation: Orientation::Rows,
+            line_style: None,
+            post_render: None,
+            markup: false,
+            ragged: false,
+            trim_trailing_whitespace: false,
+            collapse_blank_lines: false,
+            trim_blank_lines: false,
+            line_spacing: 0,
+            column_divider: default::DEFAULT_COLUMN_DIVIDER,
+            align_numbers: false,
+            indent: 0,
+            row_prefix: "",
+            row_suffix: "",
+            strip_styles: false,
+            border: None,
+            measurer: None,
+            overflow: OverflowPolicy::Truncate,
+            truncate_caption: false,
+            padding_fill: None,
+            charset: Charset::Unicode,
+            expand_sections: false,
+            trim_internal_frames: false,
+            dedupe_lines: false,
+            line_numbers: false,
+            highlighter: None,
+            max_width: None,
+            ellipsis: None,
+            width: None,
+            min_width: None,
+            alignment: Alignment::Left,
+            vertical_padding: 0,
+            caption_alignment: Alignment::Left,
+            tab_width: None,
+            control_chars: ControlPolicy::Keep,
+            line_ending: "\n",
+            trailing_newline: false,
+        }
+    }
+
+    /// Create a text box config drawn with the ASCII characters.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let msg = generate_with_config(&[String::from("plain")], TextBoxConfig::ascii().with_caption("txt"));
+    ///
+    /// assert_eq!(msg, "\
+    /// +-------+
+    /// | plain |
+    /// <txt>---+");
+    /// ```
+    pub const fn ascii() -> Self {
+        Self {
+            horizontal_bar: ascii::ASCII_HORIZONTAL_BAR,
+            vertical_bar: ascii::ASCII_VERTICAL_BAR,
+            left_top_corner: ascii::ASCII_CORNER,
+            left_bottom_corner: ascii::ASCII_CORNER,
+            right_top_corner: ascii::ASCII_CORNER,
+            right_bottom_corner: ascii::ASCII_CORNER,
+            left_tee: ascii::ASCII_CORNER,
+            right_tee: ascii::ASCII_CORNER,
+            tail: ascii::ASCII_TAIL,
+            column_divider: ascii::ASCII_VERTICAL_BAR,
+            ..Self::new()
+        }
+    }
+
+    /// Create a DOS-styled text box config.
+    pub const fn dos() -> Self {
+        Self {
+            horizontal_bar: dos::DOS_HORIZONTAL_BAR,
+            vertical_bar: dos::DOS_VERTICAL_BAR,
+            left_top_corner: dos::DOS_LEFT_TOP_CORNER,
+            left_bottom_corner: dos::DOS_LEFT_BOTTOM_CORNER,
+            right_top_corner: dos::DOS_RIGHT_TOP_CORNER,
+            right_bottom_corner: dos::DOS_RIGHT_BOTTOM_CORNER,
+            left_tee: dos::DOS_LEFT_TEE,
+            right_tee: dos::DOS_RIGHT_TEE,
+            tail: dos::DOS_TAIL,
+            column_divider: dos::DOS_COLUMN_DIVIDER,
+            ..Self::new()
+        }
+    }
+
+    /// Add the given caption to the current text box configuration.
+    pub const fn with_caption(self, caption: &'a str) -> Self {
+        Self {
+            last_line_caption: Some(caption),
+            ..self
+        }
+    }
+
+    /// Display the timestamp returned by the given provider in the top right corner of the box.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let now = || String::from("2021-03-14 15:09:26");
+    /// let msg = generate_with_config(
+    ///     &[String::from("Jobs completed: 42")],
+    ///     TextBoxConfig::default().with_timestamp(&now),
+    /// );
+    ///
+    /// assert_eq!(msg, "\
+    /// ╭─────────────────────╮
+    /// │ 2021-03-14 15:09:26 │
+    /// │ Jobs completed: 42  │
+    /// ╰─────────────────────╯");
+    /// ```
+    pub const fn with_timestamp(self, timestamp: &'a (dyn Fn() -> String + Sync)) -> Self {
+        Self {
+            timestamp: Some(timestamp),
+            ..self
+        }
+    }
+
+    /// Display the fields returned by the given provider on the last content row of the box.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let metadata = || vec![String::from("v0.1.0"), String::from("build-01"), String::from("3.2s")];
+    /// let msg = generate_with_config(
+    ///     &[String::from("Build finished")],
+    ///     TextBoxConfig::default().with_footer(&metadata),
+    /// );
+    ///
+    /// assert_eq!(msg, "\
+    /// ╭──────────────────────────╮
+    /// │ Build finished           │
+    /// ├──────────────────────────┤
+    /// │ v0.1.0 | build-01 | 3.2s │
+    /// ╰──────────────────────────╯");
+    /// ```
+    pub const fn with_footer(self, footer: &'a (dyn Fn() -> Vec<String> + Sync)) -> Self {
+        Self {
+            footer: Some(footer),
+            ..self
+        }
+    }
+
+    /// Render the box as a speech bubble whose tail starts at the given column.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let msg = generate_with_config(
+    ///     &[String::from("This value here")],
+    ///     TextBoxConfig::default().with_tail(4),
+    /// );
+    ///
+    /// assert_eq!(msg, "\
+    /// ╭─────────────────╮
+    /// │ This value here │
+    /// ╰─────────────────╯
+    ///     ╰─▸");
+    /// ```
+    pub const fn with_tail(self, column: usize) -> Self {
+        Self {
+            tail_column: Some(column),
+            ..self
+        }
+    }
+
+    /// Mirror the layout of the box for right-to-left languages.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let msg = generate_with_config(
+    ///     &[String::from("abc"), String::from("abcdef")],
+    ///     TextBoxConfig::default().with_caption("rtl").mirrored(),
+    /// );
+    ///
+    /// assert_eq!(msg, "\
+    /// ╭────────╮
+    /// │    abc │
+    /// │ abcdef │
+    /// ╰────<rtl>");
+    /// ```
+    pub const fn mirrored(self) -> Self {
+        Self {
+            mirrored: true,
+            ..self
+        }
+    }
+
+    /// Merge the given caption into the given side border, one character per row.
+    /// The box is extended with empty rows if the caption is taller than the content.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let msg = generate_with_config(
+    ///     &[String::from("CPU:    12%"), String::from("Memory: 48%")],
+    ///     TextBoxConfig::default().with_side_caption("LOG", Side::Right),
+    /// );
+    ///
+    /// assert_eq!(msg, "\
+    /// ╭─────────────╮
+    /// │ CPU:    12% L
+    /// │ Memory: 48% O
+    /// │             G
+    /// ╰─────────────╯");
+    /// ```
+    pub const fn with_side_caption(self, caption: &'a str, border: Side) -> Self {
+        Self {
+            side_caption: Some(caption),
+            side_caption_border: border,
+            ..self
+        }
+    }
+
+    /// Render each line as a column, with the columns placed side by side.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let msg = generate_with_config(
+    ///     &[String::from("abc"), String::from("de"), String::from("fghi")],
+    ///     TextBoxConfig::default().columns(),
+    /// );
+    ///
+    /// assert_eq!(msg, "\
+    /// ╭───────────╮
+    /// │ a │ d │ f │
+    /// │ b │ e │ g │
+    /// │ c │   │ h │
+    /// │   │   │ i │
+    /// ╰───────────╯");
+    /// ```
+    pub const fn columns(self) -> Self {
+        Self {
+            orientation: Orientation::Columns,
+            ..self
+        }
+    }
+
+    /// Decide how every input line is displayed with the given hook.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let highlight = |_: usize, line: &str| {
+    ///     if line.starts_with("ERROR") {
+    ///         LineStyle::new().with_prefix("! ").with_style(Style::new().fg(Color::Red))
+    ///     } else {
+    ///         LineStyle::new().with_prefix("  ")
+    ///     }
+    /// };
+    /// let msg = generate_with_config(
+    ///     &[String::from("INFO started"), String::from("ERROR failed")],
+    ///     TextBoxConfig::default().with_line_style(&highlight),
+    /// );
+    ///
+    /// assert_eq!(msg, "\
+    /// ╭────────────────╮
+    /// │   INFO started │
+    /// │ \x1b[31m! ERROR failed\x1b[0m │
+    /// ╰────────────────╯");
+    /// ```
+    pub const fn with_line_style(self, line_style: &'a LineStyleHook<'a>) -> Self {
+        Self {
+            line_style: Some(line_style),
+            ..self
+        }
+    }
+
+    /// Transform every finished output row with the given hook.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let prefix = |_: usize, kind: RowKind, row: String| match kind {
+    ///     RowKind::Content => format!("[app] {}", row),
+    ///     _ => format!("      {}", row),
+    /// };
+    /// let msg = generate_with_config(
+    ///     &[String::from("Ready")],
+    ///     TextBoxConfig::default().with_post_render(&prefix),
+    /// );
+    ///
+    /// assert_eq!(msg, "      ╭───────╮
+    /// [app] │ Ready │
+    ///       ╰───────╯");
+    /// ```
+    pub const fn with_post_render(self, post_render: &'a PostRenderHook<'a>) -> Self {
+        Self {
+            post_render: Some(post_render),
+            ..self
+        }
+    }
+
+    /// Parse every input line as the inline markup, see [`StyledLine::from_markup`].
+    /// The markup isn't counted towards the width of the box. Has no effect in the column mode.
+    /// The markup replaces the [highlighter](Self::with_highlighter): the lines aren't highlighted.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let msg = generate_with_config(
+    ///     &[String::from("**Build** [green]passed[/green]")],
+    ///     TextBoxConfig::default().with_markup(),
+    /// );
+    ///
+    /// assert_eq!(msg, "\
+    /// ╭──────────────╮
+    /// │ \x1b[1mBuild\x1b[0m \x1b[32mpassed\x1b[0m │
+    /// ╰──────────────╯");
+    /// ```
+    pub const fn with_markup(self) -> Self {
+        Self {
+            markup: true,
+            ..self
+        }
+    }
+
+    /// Omit the right border and the trailing padding, keeping the left border and the horizontal rules.
+    /// The side caption isn't displayed on the missing border.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let msg = generate_with_config(
+    ///     &[String::from("first"), String::from("second line")],
+    ///     TextBoxConfig::default().with_caption("Notes").ragged(),
+    /// );
+    ///
+    /// assert_eq!(msg, "\
+    /// ╭─────────────
+    /// │ first
+    /// │ second line
+    /// <Notes>───────");
+    /// ```
+    pub const fn ragged(self) -> Self {
+        Self {
+            ragged: true,
+            ..self
+        }
+    }
+
+    /// Remove the trailing spaces and tabs of every input line before measuring it,
+    /// so that accidental trailing whitespace doesn't widen the box.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let msg = generate_with_config(
+    ///     &[String::from("Call stack size: 1024    "), String::from("Optimization level: 1\t")],
+    ///     TextBoxConfig::default().trim_trailing_whitespace(),
+    /// );
+    ///
+    /// assert_eq!(msg, "\
+    /// ╭───────────────────────╮
+    /// │ Call stack size: 1024 │
+    /// │ Optimization level: 1 │
+    /// ╰───────────────────────╯");
+    /// ```
+    pub const fn trim_trailing_whitespace(self) -> Self {
+        Self {
+            trim_trailing_whitespace: true,
+            ..self
+        }
+    }
+
+    /// Replace every run of consecutive blank input lines with its first line.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let lines = ["a", "", "   ", "", "b"].iter().map(|s| s.to_string()).collect::<Vec<_>>();
+    /// let msg = generate_with_config(&lines, TextBoxConfig::default().collapse_blank_lines());
+    ///
+    /// assert_eq!(msg, "\
+    /// ╭───╮
+    /// │ a │
+    /// │   │
+    /// │ b │
+    /// ╰───╯");
+    /// ```
+    pub const fn collapse_blank_lines(self) -> Self {
+        Self {
+            collapse_blank_lines: true,
+            ..self
+        }
+    }
+
+    /// Remove the blank input lines at the start and at the end of the box.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let lines = ["", "a", "", "b", " "].iter().map(|s| s.to_string()).collect::<Vec<_>>();
+    /// let msg = generate_with_config(&lines, TextBoxConfig::default().trim_blank_lines());
+    ///
+    /// assert_eq!(msg, "\
+    /// ╭───╮
+    /// │ a │
+    /// │   │
+    /// │ b │
+    /// ╰───╯");
+    /// ```
+    pub const fn trim_blank_lines(self) -> Self {
+        Self {
+            trim_blank_lines: true,
+            ..self
+        }
+    }
+
+    /// Insert the given number of blank rows between the rows of the input lines.
+    /// Has no effect in the column mode.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let msg = generate_with_config(
+    ///     &[String::from("Simplicity is"), String::from("prerequisite for reliability")],
+    ///     TextBoxConfig::default().with_line_spacing(1),
+    /// );
+    ///
+    /// assert_eq!(msg, "\
+    /// ╭──────────────────────────────╮
+    /// │ Simplicity is                │
+    /// │                              │
+    /// │ prerequisite for reliability │
+    /// ╰──────────────────────────────╯");
+    /// ```
+    pub const fn with_line_spacing(self, line_spacing: usize) -> Self {
+        Self {
+            line_spacing,
+            ..self
+        }
+    }
+
+    /// Right-align the values that look like numbers, such as `1,024`, `-3.5`, or `97%`,
+    /// within the columns of the columnar layouts like [`generate_split`] and [`generate_flow`],
+    /// so that they line up on their least significant digit.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let names = [String::from("requests"), String::from("errors")];
+    /// let counts = [String::from("10,240"), String::from("3")];
+    /// let msg = generate_split(&names, &counts, TextBoxConfig::default().align_numbers());
+    ///
+    /// assert_eq!(msg, "\
+    /// ╭───────────────────╮
+    /// │ requests │ 10,240 │
+    /// │ errors   │      3 │
+    /// ╰───────────────────╯");
+    /// ```
+    pub const fn align_numbers(self) -> Self {
+        Self {
+            align_numbers: true,
+            ..self
+        }
+    }
+
+    /// Insert the given number of spaces before every output row, borders and tail included.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let msg = generate_with_config(&[String::from("nested")], TextBoxConfig::default().with_indent(4));
+    ///
+    /// assert_eq!(msg, "    ╭────────╮\n    │ nested │\n    ╰────────╯");
+    /// ```
+    pub const fn with_indent(self, indent: usize) -> Self {
+        Self { indent, ..self }
+    }
+
+    /// Attach the given prefix and suffix around every output row, outside the borders.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let msg = generate_with_config(
+    ///     &[String::from("done")],
+    ///     TextBoxConfig::default().with_row_decorators("echo '", "' \\"),
+    /// );
+    ///
+    /// assert_eq!(msg, "\
+    /// echo '╭──────╮' \\
+    /// echo '│ done │' \\
+    /// echo '╰──────╯' \\");
+    /// ```
+    pub const fn with_row_decorators(self, prefix: &'a str, suffix: &'a str) -> Self {
+        Self {
+            row_prefix: prefix,
+            row_suffix: suffix,
+            ..self
+        }
+    }
+
+    /// Display the content without the terminal styles applied by the line style hook,
+    /// the markup, and the styled lines.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let msg = generate_with_config(
+    ///     &[String::from("**bold**")],
+    ///     TextBoxConfig::default().with_markup().strip_styles(),
+    /// );
+    ///
+    /// assert_eq!(msg, "\
+    /// ╭──────╮
+    /// │ bold │
+    /// ╰──────╯");
+    /// ```
+    pub const fn strip_styles(self) -> Self {
+        Self {
+            strip_styles: true,
+            ..self
+        }
+    }
+
+    /// Replace the corners of the box with the given decorative glyphs, keeping the bars.
+    /// Returns `None` if one of the glyphs doesn't take exactly one column, which would
+    /// misalign the borders. The glyphs are measured by the [`measurer`](Self::measurer)
+    /// of the config, counting the characters by default.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let config = TextBoxConfig::default().with_corners("✦", "✦", "✦", "✦").unwrap();
+    ///
+    /// assert_eq!(generate_with_config(&[String::from("Badge")], config), "\
+    /// ✦───────✦
+    /// │ Badge │
+    /// ✦───────✦");
+    /// assert!(TextBoxConfig::default().with_corners("<<", ">>", "<", ">").is_none());
+    /// ```
+    pub fn with_corners(
+        self,
+        left_top: &'a str,
+        right_top: &'a str,
+        left_bottom: &'a str,
+        right_bottom: &'a str,
+    ) -> Option<Self> {
+        let measure = self.measurer.unwrap_or(&CharWidth);
+        let corners = [left_top, right_top, left_bottom, right_bottom];
+        if corners.iter().any(|corner| measure.width(corner) != 1) {
+            return None;
+        }
+
+        Some(Self {
+            left_top_corner: left_top,
+            right_top_corner: right_top,
+            left_bottom_corner: left_bottom,
+            right_bottom_corner: right_bottom,
+            ..self
+        })
+    }
+
+    /// Draw the border of the box with the given [`Border`] instead of the box drawing characters.
+    pub const fn with_border(self, border: &'a (dyn Border + Sync)) -> Self {
+        Self {
+            border: Some(border),
+            ..self
+        }
+    }
+
+    /// Measure the width of the text with the given [`WidthMeasurer`] instead of counting its bytes.
+    pub const fn with_measurer(self, measurer: &'a (dyn WidthMeasurer + Sync)) -> Self {
+        Self {
+            measurer: Some(measurer),
+            ..self
+        }
+    }
+
+    /// Skip the ANSI escape sequences, like the colors and the hyperlinks, when measuring
+    /// the lines, counting every other character as one column. The sequences are kept
+    /// in the output. See [`AnsiWidth`] for the other measurers of the visible text.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let lines = [String::from("\x1b[31merror\x1b[0m: disk full"), String::from("retrying")];
+    /// let msg = generate_with_config(&lines, TextBoxConfig::default().ansi_aware());
+    ///
+    /// assert_eq!(msg, "\
+    /// ╭──────────────────╮
+    /// │ \x1b[31merror\x1b[0m: disk full │
+    /// │ retrying         │
+    /// ╰──────────────────╯");
+    /// ```
+    pub const fn ansi_aware(self) -> Self {
+        self.with_measurer(&AnsiWidth(CharWidth))
+    }
+
+    /// Measure the text as the terminals display it with [`UnicodeWidth`], so the lines with
+    /// the wide East Asian characters, the combining accents and the emoji are padded correctly.
+    /// Enabled by the `unicode-width` feature.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let lines = ["日本語", "cafe\u{301}", "🦀 ok"].map(String::from);
+    /// let msg = generate_with_config(&lines, TextBoxConfig::default().unicode_width());
+    ///
+    /// assert_eq!(msg, "\
+    /// ╭────────╮
+    /// │ 日本語 │
+    /// │ cafe\u{301}   │
+    /// │ 🦀 ok  │
+    /// ╰────────╯");
+    /// ```
+    #[cfg(feature = "unicode-width")]
+    pub const fn unicode_width(self) -> Self {
+        self.with_measurer(&UnicodeWidth)
+    }
+
+    /// The measurer of the text width, [`ByteWidth`] unless the config has its own.
+    pub(crate) fn measure(&self) -> measure::Measure<'a> {
+        self.measurer.unwrap_or(&ByteWidth)
+    }
+
+    /// Set what happens to the lines that don't fit into a box with a fixed width,
+    /// see [`Renderer::with_width`].
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let config = TextBoxConfig::default().with_overflow(OverflowPolicy::Wrap);
+    /// let renderer = Renderer::with_width(config, 16);
+    ///
+    /// assert_eq!(renderer.render(&[String::from("Simplicity is prerequisite")]), "\
+    /// ╭──────────────╮
+    /// │ Simplicity   │
+    /// │ is           │
+    /// │ prerequisite │
+    /// ╰──────────────╯");
+    /// ```
+    pub const fn with_overflow(self, overflow: OverflowPolicy) -> Self {
+        Self { overflow, ..self }
+    }
+
+    /// Truncate the caption to the width of the content instead of widening the box to fit it,
+    /// so that the boxes with short lines stay narrow even with a verbose caption.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let config = TextBoxConfig::default()
+    ///     .with_caption("Deployment finished")
+    ///     .truncate_caption();
+    /// let msg = generate_with_config(&[String::from("3 services up")], config);
+    ///
+    /// assert_eq!(msg, "\
+    /// ╭───────────────╮
+    /// │ 3 services up │
+    /// <Deployment fi>─╯");
+    /// ```
+    pub const fn truncate_caption(self) -> Self {
+        Self {
+            truncate_caption: true,
+            ..self
+        }
+    }
+
+    /// Fill the padding of the content rows with the given pattern instead of the spaces,
+    /// for example to watermark a report. The pattern is lined up across the rows, and
+    /// each of its characters is expected to take one column.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let lines = ["Q3", "Revenue: 12%"].map(String::from);
+    /// let msg = generate_with_config(&lines, TextBoxConfig::default().with_padding_fill("DRAFT "));
+    ///
+    /// assert_eq!(msg, "\
+    /// ╭──────────────╮
+    /// │ Q3AFT DRAFT  │
+    /// │ Revenue: 12% │
+    /// ╰──────────────╯");
+    /// ```
+    pub const fn with_padding_fill(self, pattern: &'a str) -> Self {
+        Self {
+            padding_fill: Some(pattern),
+            ..self
+        }
+    }
+
+    /// Draw the glyphs of the box with the given charset, replacing the ones it can't display,
+    /// so that the same config works in the modern terminals and in the legacy consoles.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let config = TextBoxConfig::default().with_caption("log");
+    ///
+    /// assert_eq!(generate_with_config(&[String::from("ok")], config.with_charset(Charset::Ascii)), "\
+    /// +-----+
+    /// | ok  |
+    /// <log>-+");
+    /// ```
+    pub const fn with_charset(self, charset: Charset) -> Self {
+        Self { charset, ..self }
+    }
+
+    /// Expand the collapsed sections, see [`generate_sections`].
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let lines = vec![String::from("details")];
+    /// let sections = [Section::new("Log", lines).collapsed()];
+    ///
+    /// assert_eq!(generate_sections(&sections, TextBoxConfig::default().expand_sections()), "\
+    /// ╭─────────╮
+    /// │ v Log   │
+    /// │ details │
+    /// ╰─────────╯");
+    /// ```
+    pub const fn expand_sections(self) -> Self {
+        Self {
+            expand_sections: true,
+            ..self
+        }
+    }
+
+    /// Skip the frames of the Rust runtime and of the standard library, see [`box_backtrace`].
+    pub const fn trim_internal_frames(self) -> Self {
+        Self {
+            trim_internal_frames: true,
+            ..self
+        }
+    }
+
+    /// Replace every run of consecutive identical lines with its first line suffixed with
+    /// the number of the lines, like `×3`. The sign is spelled as `x` unless the measurer
+    /// counts it as one column. The blank lines are never merged.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let lines = ["tick", "tick", "tick", "done"].map(String::from);
+    /// let msg = generate_with_config(&lines, TextBoxConfig::default().dedupe_lines());
+    ///
+    /// assert_eq!(msg, "\
+    /// ╭─────────╮
+    /// │ tick x3 │
+    /// │ done    │
+    /// ╰─────────╯");
+    /// ```
+    pub const fn dedupe_lines(self) -> Self {
+        Self {
+            dedupe_lines: true,
+            ..self
+        }
+    }
+
+    /// Prefix every line of the code boxes rendered by `box_code` with its number.
+    pub const fn line_numbers(self) -> Self {
+        Self {
+            line_numbers: true,
+            ..self
+        }
+    }
+
+    /// Highlight the parts of the content lines found by the given highlighter. Without styles,
+    /// the highlighted parts are surrounded with `*` instead. The lines of the
+    /// [markup](Self::with_markup) aren't highlighted.
+    pub const fn with_highlighter(self, highlighter: &'a (dyn Highlighter + Sync)) -> Self {
+        Self {
+            highlighter: Some(highlighter),
+            ..self
+        }
+    }
+
+    /// Never make the box wider than `width` columns, borders included: the longer lines are
+    /// wrapped at the word boundaries, and the caption is truncated. The box is still
+    /// fitted to its content if it's narrower.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let lines = [String::from("Connection refused, retrying in 5 seconds")];
+    /// let msg = generate_with_config(&lines, TextBoxConfig::default().with_max_width(24));
+    ///
+    /// assert_eq!(msg, "\
+    /// ╭─────────────────────╮
+    /// │ Connection refused, │
+    /// │ retrying in 5       │
+    /// │ seconds             │
+    /// ╰─────────────────────╯");
+    /// ```
+    pub const fn with_max_width(self, width: usize) -> Self {
+        Self {
+            max_width: Some(width),
+            ..self
+        }
+    }
+
+    /// End the lines that are truncated to fit into the box with the given ellipsis, like
+    /// `"..."` or `"…"`. The ellipsis is measured like the lines, so the non-ASCII ones
+    /// need a [measurer](Self::with_measurer) like [`CharWidth`].
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let lines = [String::from("cpu: 97%, mem: 12 GiB")];
+    /// let config = TextBoxConfig::default().with_measurer(&CharWidth).with_ellipsis("…");
+    ///
+    /// assert_eq!(Renderer::with_width(config, 14).render(&lines), "\
+    /// ╭────────────╮
+    /// │ cpu: 97%,… │
+    /// ╰────────────╯");
+    /// ```
+    pub const fn with_ellipsis(self, ellipsis: &'a str) -> Self {
+        Self {
+            ellipsis: Some(ellipsis),
+            ..self
+        }
+    }
+
+    /// Make every box exactly `width` columns wide, borders included, like
+    /// [`Renderer::with_width`]: the shorter lines are padded, and the longer ones are
+    /// truncated, or wrapped with [`OverflowPolicy::Wrap`].
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let config = TextBoxConfig::default().with_width(16);
+    /// assert_eq!(generate_with_config(&[String::from("OK")], config), "\
+    /// ╭──────────────╮
+    /// │ OK           │
+    /// ╰──────────────╯");
+    ///
+    /// let lines = [String::from("disk usage above 90%")];
+    /// let wrapped = config.with_overflow(OverflowPolicy::Wrap);
+    /// assert_eq!(generate_with_config(&lines, wrapped), "\
+    /// ╭──────────────╮
+    /// │ disk usage   │
+    /// │ above 90%    │
+    /// ╰──────────────╯");
+    /// ```
+    pub const fn with_width(self, width: usize) -> Self {
+        Self {
+            width: Some(width),
+            ..self
+        }
+    }
+
+    /// Never make the box narrower than `width` columns, borders included: the box is
+    /// padded to the width when its content is shorter, and fitted to its content otherwise.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let config = TextBoxConfig::default().with_min_width(12);
+    ///
+    /// assert_eq!(generate_with_config(&[String::from("OK")], config), "\
+    /// ╭──────────╮
+    /// │ OK       │
+    /// ╰──────────╯");
+    /// assert_eq!(generate_with_config(&[String::from("Failed: 3")], config), "\
+    /// ╭───────────╮
+    /// │ Failed: 3 │
+    /// ╰───────────╯");
+    /// ```
+    pub const fn with_min_width(self, width: usize) -> Self {
+        Self {
+            min_width: Some(width),
+            ..self
+        }
+    }
+
+    /// Align every content line in the given way, unless its [`LineStyle`] or [`Line`]
+    /// overrides it. The alignments are swapped in the [mirrored](Self::mirrored) boxes.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let lines = [String::from("Release 2.0"), String::from("is out!")];
+    /// let config = TextBoxConfig::default().with_alignment(Alignment::Center);
+    ///
+    /// assert_eq!(generate_with_config(&lines, config), "\
+    /// ╭─────────────╮
+    /// │ Release 2.0 │
+    /// │   is out!   │
+    /// ╰─────────────╯");
+    /// ```
+    pub const fn with_alignment(self, alignment: Alignment) -> Self {
+        Self { alignment, ..self }
+    }
+
+    /// Insert the given number of blank rows after the top border and before the bottom
+    /// border of the box.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let config = TextBoxConfig::default().with_vertical_padding(1);
+    ///
+    /// assert_eq!(generate_with_config(&[String::from("Deploy frozen")], config), "\
+    /// ╭───────────────╮
+    /// │               │
+    /// │ Deploy frozen │
+    /// │               │
+    /// ╰───────────────╯");
+    /// ```
+    pub const fn with_vertical_padding(self, vertical_padding: usize) -> Self {
+        Self {
+            vertical_padding,
+            ..self
+        }
+    }
+
+    /// Position the caption within the bottom border in the given way: in place of the left
+    /// corner, between the corners, or in place of the right corner. The alignments are
+    /// swapped in the [mirrored](Self::mirrored) boxes.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let lines = [String::from("All checks passed")];
+    /// let config = TextBoxConfig::default().with_caption("CI");
+    ///
+    /// let centered = config.with_caption_alignment(Alignment::Center);
+    /// assert_eq!(generate_with_config(&lines, centered), "\
+    /// ╭───────────────────╮
+    /// │ All checks passed │
+    /// ╰───────<CI>────────╯");
+    ///
+    /// let right = config.with_caption_alignment(Alignment::Right);
+    /// assert_eq!(generate_with_config(&lines, right).lines().last(), Some("╰────────────────<CI>"));
+    /// ```
+    pub const fn with_caption_alignment(self, caption_alignment: Alignment) -> Self {
+        Self {
+            caption_alignment,
+            ..self
+        }
+    }
+
+    /// Expand the tabs of the lines to the spaces up to the next tab stop, placed every
+    /// `tab_width` columns, before the lines are measured. The terminals display the tabs
+    /// wider than a column, which would otherwise misalign the right border.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let lines = [String::from("name\tsize"), String::from("a.rs\t12")];
+    /// let msg = generate_with_config(&lines, TextBoxConfig::ascii().with_tab_width(8));
+    ///
+    /// assert_eq!(msg, "\
+    /// +--------------+
+    /// | name    size |
+    /// | a.rs    12   |
+    /// +--------------+");
+    /// ```
+    pub const fn with_tab_width(self, tab_width: usize) -> Self {
+        Self {
+            tab_width: Some(tab_width),
+            ..self
+        }
+    }
+
+    /// Strip or escape the control characters of the lines according to the policy,
+    /// so a box displaying the untrusted text like the log lines keeps its frame intact.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let lines = [String::from("\x1b[31mfailed\x1b[0m\r\0")];
+    /// let config = TextBoxConfig::ascii();
+    ///
+    /// let stripped = config.with_control_chars(ControlPolicy::Strip);
+    /// assert_eq!(generate_with_config(&lines, stripped), "\
+    /// +--------+
+    /// | failed |
+    /// +--------+");
+    ///
+    /// let escaped = config.with_control_chars(ControlPolicy::Escape);
+    /// let msg = generate_with_config(&lines, escaped);
+    /// assert_eq!(msg.lines().nth(1), Some(r"| \x1b[31mfailed\x1b[0m\r\0 |"));
+    /// ```
+    pub const fn with_control_chars(self, control_chars: ControlPolicy) -> Self {
+        Self {
+            control_chars,
+            ..self
+        }
+    }
+
+    /// Separate the output rows with the given line ending, like `"\r\n"` for the files
+    /// read on Windows.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let lines = [String::from("ok")];
+    /// let msg = generate_with_config(&lines, TextBoxConfig::ascii().with_line_ending("\r\n"));
+    /// assert_eq!(msg, "+----+\r\n| ok |\r\n+----+");
+    /// ```
+    pub const fn with_line_ending(self, line_ending: &'a str) -> Self {
         Self {
-            horizontal_bar: dos::DOS_HORIZONTAL_BAR,
-            vertical_bar: dos::DOS_VERTICAL_BAR,
-            left_top_corner: dos::DOS_LEFT_TOP_CORNER,
-            left_bottom_corner: dos::DOS_LEFT_BOTTOM_CORNER,
-            right_top_corner: dos::DOS_RIGHT_TOP_CORNER,
-            right_bottom_corner: dos::DOS_RIGHT_BOTTOM_CORNER,
-            last_line_caption: None,
+            line_ending,
+            ..self
         }
     }
 
-    /// Add the given caption to the current text box configuration.
-    pub fn with_caption(self, caption: &'a str) -> Self {
+    /// End the output with a line ending after the last row, so the boxes can be written
+    /// to the files or concatenated as is.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let lines = [String::from("ok")];
+    /// let config = TextBoxConfig::ascii().with_line_ending("\r\n").trailing_newline();
+    /// assert_eq!(generate_with_config(&lines, config), "+----+\r\n| ok |\r\n+----+\r\n");
+    /// ```
+    pub const fn trailing_newline(self) -> Self {
         Self {
-            last_line_caption: Some(caption),
+            trailing_newline: true,
             ..self
         }
     }
 }
 
+impl<'a> fmt::Debug for TextBoxConfig<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TextBoxConfig")
+            .field("horizontal_bar", &self.horizontal_bar)
+            .field("vertical_bar", &self.vertical_bar)
+            .field("left_top_corner", &self.left_top_corner)
+            .field("left_bottom_corner", &self.left_bottom_corner)
+            .field("right_top_corner", &self.right_top_corner)
+            .field("right_bottom_corner", &self.right_bottom_corner)
+            .field("left_tee", &self.left_tee)
+            .field("right_tee", &self.right_tee)
+            .field("tail", &self.tail)
+            .field("last_line_caption", &self.last_line_caption)
+            .field("timestamp", &self.timestamp.map(|_| ".."))
+            .field("footer", &self.footer.map(|_| ".."))
+            .field("tail_column", &self.tail_column)
+            .field("mirrored", &self.mirrored)
+            .field("side_caption", &self.side_caption)
+            .field("side_caption_border", &self.side_caption_border)
+            .field("orientation", &self.orientation)
+            .field("line_style", &self.line_style.map(|_| ".."))
+            .field("post_render", &self.post_render.map(|_| ".."))
+            .field("markup", &self.markup)
+            .field("ragged", &self.ragged)
+            .field("trim_trailing_whitespace", &self.trim_trailing_whitespace)
+            .field("collapse_blank_lines", &self.collapse_blank_lines)
+            .field("trim_blank_lines", &self.trim_blank_lines)
+            .field("line_spacing", &self.line_spacing)
+            .field("column_divider", &self.column_divider)
+            .field("align_numbers", &self.align_numbers)
+            .field("indent", &self.indent)
+            .field("row_prefix", &self.row_prefix)
+            .field("row_suffix", &self.row_suffix)
+            .field("strip_styles", &self.strip_styles)
+            .field("border", &self.border.map(|_| ".."))
+            .field("measurer", &self.measurer.map(|_| ".."))
+            .field("overflow", &self.overflow)
+            .field("truncate_caption", &self.truncate_caption)
+            .field("padding_fill", &self.padding_fill)
+            .field("charset", &self.charset)
+            .field("expand_sections", &self.expand_sections)
+            .field("trim_internal_frames", &self.trim_internal_frames)
+            .field("dedupe_lines", &self.dedupe_lines)
+            .field("line_numbers", &self.line_numbers)
+            .field("highlighter", &self.highlighter.map(|_| ".."))
+            .field("max_width", &self.max_width)
+            .field("ellipsis", &self.ellipsis)
+            .field("width", &self.width)
+            .field("min_width", &self.min_width)
+            .field("alignment", &self.alignment)
+            .field("vertical_padding", &self.vertical_padding)
+            .field("caption_alignment", &self.caption_alignment)
+            .field("tab_width", &self.tab_width)
+            .field("control_chars", &self.control_chars)
+            .field("line_ending", &self.line_ending)
+            .field("trailing_newline", &self.trailing_newline)
+            .finish()
+    }
+}
+
 impl<'a> Default for TextBoxConfig<'a> {
     fn default() -> Self {
-        Self {
-            horizontal_bar: default::DEFAULT_HORIZONTAL_BAR,
-            vertical_bar: default::DEFAULT_VERTICAL_BAR,
-            left_top_corner: default::DEFAULT_LEFT_TOP_CORNER,
-            left_bottom_corner: default::DEFAULT_LEFT_BOTTOM_CORNER,
-            right_top_corner: default::DEFAULT_RIGHT_TOP_CORNER,
-            right_bottom_corner: default::DEFAULT_RIGHT_BOTTOM_CORNER,
-            last_line_caption: None,
+        Self::new()
+    }
+}
+
+/// The alignment of the content within the box.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Alignment {
+    /// Align to the left border.
+    Left,
+    /// Center between the borders, leaning to the left.
+    Center,
+    /// Align to the right border.
+    Right,
+}
+
+impl Alignment {
+    /// Swap the left and the right alignments.
+    pub fn opposite(self) -> Self {
+        match self {
+            Alignment::Left => Alignment::Right,
+            Alignment::Center => Alignment::Center,
+            Alignment::Right => Alignment::Left,
         }
     }
 }
 
+/// The kind of a rendered row of the box.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RowKind {
+    /// The top border or the bottom border without a caption.
+    Border,
+    /// A row with the content between the vertical bars.
+    Content,
+    /// A horizontal rule between the content rows.
+    Separator,
+    /// The bottom border with the caption.
+    Caption,
+    /// The speech bubble tail below the box.
+    Tail,
+}
+
+/// A side of the box.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    /// The left side.
+    Left,
+    /// The right side.
+    Right,
+}
+
+/// What happens to the lines that don't fit into a box with a fixed width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// The lines are cut at the right border.
+    Truncate,
+    /// The lines are broken at the whitespace into several rows, and the words that are
+    /// still too long are broken at the right border.
+    Wrap,
+    /// The box is widened to fit the lines and the caption, like the boxes without a fixed width.
+    Expand,
+}
+
+/// What happens to the control characters of the lines, like `\r`, `\0`, or the escape
+/// character starting the terminal escape sequences, which could corrupt the box.
+/// The tabs are left to [`tab_width`](TextBoxConfig::tab_width), and the embedded
+/// line endings always split the lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ControlPolicy {
+    /// The control characters are displayed as is.
+    Keep,
+    /// The control characters are removed, along with the rest of the escape sequences.
+    Strip,
+    /// The control characters are replaced with their escapes, like `\r` or `\x1b`.
+    Escape,
+}
+
+/// The error returned by [`Renderer::try_render`] when the caption doesn't fit into the box.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaptionOverflow {
+    /// The width of the caption.
+    pub caption_width: usize,
+    /// The content width of the box available to the caption.
+    pub available_width: usize,
+}
+
+impl fmt::Display for CaptionOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the caption is {} columns wide, but only {} columns are available",
+            self.caption_width, self.available_width
+        )
+    }
+}
+
+impl std::error::Error for CaptionOverflow {}
+
+/// The direction in which the lines of a box are laid out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    /// Every line is a row of the box.
+    Rows,
+    /// Every line is a column of the box, read top to bottom.
+    Columns,
+}
+
 /// Contains the default box characters.
 pub mod default {
     /// The default horizontal bar character `─`.
-    pub static DEFAULT_HORIZONTAL_BAR: &str = "─";
+    pub const DEFAULT_HORIZONTAL_BAR: &str = "─";
     /// The default vertical bar character `│`.
-    pub static DEFAULT_VERTICAL_BAR: &str = "│";
+    pub const DEFAULT_VERTICAL_BAR: &str = "│";
     /// The default left top corner character `╭`.
-    pub static DEFAULT_LEFT_TOP_CORNER: &str = "╭";
+    pub const DEFAULT_LEFT_TOP_CORNER: &str = "╭";
     /// The default left bottom corner character `╰`.
-    pub static DEFAULT_LEFT_BOTTOM_CORNER: &str = "╰";
+    pub const DEFAULT_LEFT_BOTTOM_CORNER: &str = "╰";
     /// The default right top corner character `╮`.
-    pub static DEFAULT_RIGHT_TOP_CORNER: &str = "╮";
+    pub const DEFAULT_RIGHT_TOP_CORNER: &str = "╮";
     /// The default right bottom corner character `╯`.
-    pub static DEFAULT_RIGHT_BOTTOM_CORNER: &str = "╯";
+    pub const DEFAULT_RIGHT_BOTTOM_CORNER: &str = "╯";
+    /// The default left tee character `├`.
+    pub const DEFAULT_LEFT_TEE: &str = "├";
+    /// The default right tee character `┤`.
+    pub const DEFAULT_RIGHT_TEE: &str = "┤";
+    /// The default column divider character `│`.
+    pub const DEFAULT_COLUMN_DIVIDER: &str = "│";
+    /// The default junction of the top border with the column dividers `┬`.
+    pub const DEFAULT_TOP_JUNCTION: &str = "┬";
+    /// The default junction of the bottom border with the column dividers `┴`.
+    pub const DEFAULT_BOTTOM_JUNCTION: &str = "┴";
+    /// The default junction of the horizontal rules with the column dividers `┼`.
+    pub const DEFAULT_CROSS: &str = "┼";
+    /// The default speech bubble tail `╰─▸`.
+    pub const DEFAULT_TAIL: &str = "╰─▸";
 }
 
 /// Contains DOS-styled box characters.
 pub mod dos {
     /// The DOS horizontal bar character `═`.
-    pub static DOS_HORIZONTAL_BAR: &str = "═";
+    pub const DOS_HORIZONTAL_BAR: &str = "═";
     /// The DOS vertical bar character `║`.
-    pub static DOS_VERTICAL_BAR: &str = "║";
+    pub const DOS_VERTICAL_BAR: &str = "║";
     /// The DOS left top corner character `╔`.
-    pub static DOS_LEFT_TOP_CORNER: &str = "╔";
+    pub const DOS_LEFT_TOP_CORNER: &str = "╔";
     /// The DOS left bottom corner character `╚`.
-    pub static DOS_LEFT_BOTTOM_CORNER: &str = "╚";
+    pub const DOS_LEFT_BOTTOM_CORNER: &str = "╚";
     /// The DOS right top corner character `╗`.
-    pub static DOS_RIGHT_TOP_CORNER: &str = "╗";
+    pub const DOS_RIGHT_TOP_CORNER: &str = "╗";
     /// The DOS right bottom corner character `╝`.
-    pub static DOS_RIGHT_BOTTOM_CORNER: &str = "╝";
+    pub const DOS_RIGHT_BOTTOM_CORNER: &str = "╝";
+    /// The DOS left tee character `╠`.
+    pub const DOS_LEFT_TEE: &str = "╠";
+    /// The DOS right tee character `╣`.
+    pub const DOS_RIGHT_TEE: &str = "╣";
+    /// The DOS column divider character `│`.
+    pub const DOS_COLUMN_DIVIDER: &str = "│";
+    /// The DOS junction of the top border with the column dividers `╤`.
+    pub const DOS_TOP_JUNCTION: &str = "╤";
+    /// The DOS junction of the bottom border with the column dividers `╧`.
+    pub const DOS_BOTTOM_JUNCTION: &str = "╧";
+    /// The DOS junction of the horizontal rules with the column dividers `╪`.
+    pub const DOS_CROSS: &str = "╪";
+    /// The DOS speech bubble tail `╚═▸`.
+    pub const DOS_TAIL: &str = "╚═▸";
+}
+
+/// Contains the ASCII box characters.
+pub mod ascii {
+    /// The ASCII horizontal bar character `-`.
+    pub const ASCII_HORIZONTAL_BAR: &str = "-";
+    /// The ASCII vertical bar character `|`.
+    pub const ASCII_VERTICAL_BAR: &str = "|";
+    /// The ASCII character `+` used for the corners and the tees.
+    pub const ASCII_CORNER: &str = "+";
+    /// The ASCII speech bubble tail `` `-> ``.
+    pub const ASCII_TAIL: &str = "`->";
+    /// The ASCII ellipsis `...`.
+    pub const ASCII_ELLIPSIS: &str = "...";
 }
 
 #[cfg(test)]
@@ -358,4 +2265,553 @@ mod tests {
 ╚══════════════════════════════════════════════════════════════════════════════════════════════════════════╝"
 )
     }
+
+    #[test]
+    fn test_timestamp_header() {
+        let timestamp = || String::from("12:00:00");
+        let config = TextBoxConfig::default().with_timestamp(&timestamp);
+
+        let short = generate_with_config(&["Up: 3d".to_string()], config);
+        assert_eq!(
+            short,
+            "\
+╭──────────╮
+│ 12:00:00 │
+│ Up: 3d   │
+╰──────────╯"
+        );
+
+        let long = generate_with_config(&["Requests served: 1024".to_string()], config);
+        assert_eq!(
+            long,
+            "\
+╭───────────────────────╮
+│              12:00:00 │
+│ Requests served: 1024 │
+╰───────────────────────╯"
+        );
+    }
+
+    #[test]
+    fn test_footer() {
+        let footer = || vec!["v1.2.3".to_string(), "localhost".to_string()];
+        let msgbox = generate_with_config(
+            &["A much longer line of the box body".to_string()],
+            TextBoxConfig::dos()
+                .with_caption("Summary")
+                .with_footer(&footer),
+        );
+
+        assert_eq!(
+            msgbox,
+            "\
+╔════════════════════════════════════╗
+║ A much longer line of the box body ║
+╠════════════════════════════════════╣
+║ v1.2.3 | localhost                 ║
+<Summary>════════════════════════════╝"
+        );
+
+        let empty = || vec![];
+        let msgbox = generate_with_config(&[], TextBoxConfig::default().with_footer(&empty));
+        assert_eq!(msgbox, "╭──╮\n├──┤\n│  │\n╰──╯");
+    }
+
+    #[test]
+    fn test_speech_bubble_tail() {
+        let msgbox = generate_with_config(
+            &["x = 42".to_string()],
+            TextBoxConfig {
+                tail: "\\",
+                ..TextBoxConfig::default().with_tail(0)
+            },
+        );
+
+        assert_eq!(
+            msgbox,
+            "\
+╭────────╮
+│ x = 42 │
+╰────────╯
+\\"
+        );
+    }
+
+    #[test]
+    fn test_mirrored() {
+        let timestamp = || "12:00".to_string();
+        let msgbox = generate_with_config(
+            &["Line 1".to_string(), "A longer line 2".to_string()],
+            TextBoxConfig::dos()
+                .with_timestamp(&timestamp)
+                .with_caption("Mirrored")
+                .mirrored(),
+        );
+
+        assert_eq!(
+            msgbox,
+            "\
+╔═════════════════╗
+║ 12:00           ║
+║          Line 1 ║
+║ A longer line 2 ║
+╚════════<Mirrored>"
+        );
+    }
+
+    #[test]
+    fn test_side_caption() {
+        let footer = || vec!["v1".to_string()];
+        let msgbox = generate_with_config(
+            &["1".to_string(), "2".to_string()],
+            TextBoxConfig::default()
+                .with_footer(&footer)
+                .with_side_caption("abcd", Side::Left),
+        );
+
+        assert_eq!(
+            msgbox,
+            "\
+╭────╮
+a 1  │
+b 2  │
+c    │
+├────┤
+d v1 │
+╰────╯"
+        );
+
+        let msgbox = generate_with_config(
+            &["1".to_string(), "2".to_string(), "3".to_string()],
+            TextBoxConfig::dos().with_side_caption("ab", Side::Right),
+        );
+
+        assert_eq!(
+            msgbox,
+            "\
+╔═══╗
+║ 1 a
+║ 2 b
+║ 3 ║
+╚═══╝"
+        );
+    }
+
+    #[test]
+    fn test_columns() {
+        let msgbox = generate_with_config(
+            &["ab".to_string(), "".to_string(), "c".to_string()],
+            TextBoxConfig::dos().columns(),
+        );
+
+        assert_eq!(
+            msgbox,
+            "\
+╔═══════════╗
+║ a ║   ║ c ║
+║ b ║   ║   ║
+╚═══════════╝"
+        );
+
+        assert_eq!(
+            generate_with_config(&[], TextBoxConfig::default().columns()),
+            generate_box(&[])
+        );
+
+        let styled = [
+            "\x1b[31mab\x1b[0m".to_string(),
+            ansi::hyperlink("c", "http://x"),
+            "e\u{301}".to_string(),
+        ];
+        assert_eq!(
+            generate_with_config(&styled, TextBoxConfig::dos().columns()),
+            "\
+╔═══════════╗
+║ a ║ c ║ e\u{301} ║
+║ b ║   ║   ║
+╚═══════════╝"
+        );
+    }
+
+    #[test]
+    fn test_huge_box() {
+        let lines = (0..50_000).map(|i| i.to_string()).collect::<Vec<_>>();
+        let msgbox = generate_with_caption(&lines, "Huge");
+        let rows = msgbox.lines().collect::<Vec<_>>();
+
+        assert_eq!(rows.len(), lines.len() + 2);
+        assert_eq!(rows[1], "│ 0     │");
+        assert_eq!(rows[50_000], "│ 49999 │");
+        assert_eq!(rows[50_001], "<Huge>──╯");
+    }
+
+    #[test]
+    fn test_group() {
+        assert!(generate_group(&[], TextBoxConfig::default()).is_empty());
+
+        let boxes = generate_group(
+            &[
+                &[],
+                &["a".to_string(), "bcd".to_string()],
+                &["ef".to_string()],
+            ],
+            TextBoxConfig::default().with_caption("C"),
+        );
+        assert_eq!(
+            boxes,
+            [
+                "╭─────╮\n<C>───╯",
+                "╭─────╮\n│ a   │\n│ bcd │\n<C>───╯",
+                "╭─────╮\n│ ef  │\n<C>───╯",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_frame() {
+        assert_eq!(
+            generate_frame(&[], TextBoxConfig::default()),
+            generate_box(&[])
+        );
+
+        let inner = generate_frame(
+            &[generate_box(&["u".to_string()])],
+            TextBoxConfig::dos().with_caption("ä"),
+        );
+        let outer = generate_frame(&[inner], TextBoxConfig::default());
+        assert_eq!(
+            outer,
+            "\
+╭───────────╮
+│ ╔═══════╗ │
+│ ║ ╭───╮ ║ │
+│ ║ │ u │ ║ │
+│ ║ ╰───╯ ║ │
+│ <ä>═════╝ │
+╰───────────╯"
+        );
+    }
+
+    #[test]
+    fn test_line_style() {
+        let prefix = String::from("- ");
+        let style = |index: usize, _: &str| match index {
+            0 => LineStyle::new().with_alignment(Alignment::Center),
+            1 => LineStyle::new().with_alignment(Alignment::Right),
+            _ => LineStyle::new().with_prefix(prefix.clone()),
+        };
+        let lines = [
+            "Title".to_string(),
+            "42".to_string(),
+            "first".to_string(),
+            "second item".to_string(),
+        ];
+
+        let expected = "\
+╭───────────────╮
+│     Title     │
+│            42 │
+│ - first       │
+│ - second item │
+╰───────────────╯";
+        assert_eq!(
+            generate_with_config(&lines, TextBoxConfig::default().with_line_style(&style)),
+            expected
+        );
+
+        let renderer = Renderer::with_width(TextBoxConfig::default().with_line_style(&style), 17);
+        let mut stream = renderer.stream(Vec::new()).unwrap();
+        for line in &lines {
+            stream.write_line(line).unwrap();
+        }
+        assert_eq!(
+            String::from_utf8(stream.finish().unwrap()).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_post_render() {
+        let kinds = std::sync::Mutex::new(Vec::new());
+        let hook = |index: usize, kind: RowKind, row: String| {
+            kinds.lock().unwrap().push((index, kind));
+            row
+        };
+        let footer = || vec!["f".to_string()];
+        let config = TextBoxConfig::default()
+            .with_post_render(&hook)
+            .with_footer(&footer)
+            .with_caption("C")
+            .with_tail(0);
+
+        let msgbox = generate_with_config(&["a".to_string()], config);
+        assert_eq!(
+            msgbox,
+            generate_with_config(
+                &["a".to_string()],
+                TextBoxConfig {
+                    post_render: None,
+                    ..config
+                }
+            )
+        );
+        assert_eq!(
+            *kinds.lock().unwrap(),
+            [
+                (0, RowKind::Border),
+                (1, RowKind::Content),
+                (2, RowKind::Separator),
+                (3, RowKind::Content),
+                (4, RowKind::Caption),
+                (5, RowKind::Tail),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ragged() {
+        let footer = || vec!["f".to_string()];
+        let config = TextBoxConfig::dos().ragged();
+        let lines = ["abc".to_string(), "".to_string()];
+
+        assert_eq!(
+            generate_with_config(&lines, config.with_footer(&footer)),
+            "\
+╔═════
+║ abc
+║
+╠═════
+║ f
+╚═════"
+        );
+        assert_eq!(
+            generate_with_config(&["a".to_string()], config.mirrored()),
+            "\
+╔═══
+║ a
+╚═══"
+        );
+    }
+
+    #[test]
+    fn test_trim_trailing_whitespace() {
+        let lines = (0..10).map(|i| format!("{} \t ", i)).collect::<Vec<_>>();
+        let trimmed = (0..10).map(|i| i.to_string()).collect::<Vec<_>>();
+        let config = TextBoxConfig::default().trim_trailing_whitespace();
+
+        assert_eq!(
+            generate_with_config(&lines[..2], config),
+            generate_box(&trimmed[..2])
+        );
+        assert_eq!(generate_with_config(&lines, config), generate_box(&trimmed));
+        assert_eq!(
+            generate_with_config(&lines[..2], config.columns()),
+            generate_with_config(&trimmed[..2], TextBoxConfig::default().columns())
+        );
+    }
+
+    #[test]
+    fn test_split() {
+        let left = ["aaaa".to_string(), "b".to_string()];
+        let right = ["c".to_string(), "dd".to_string(), "e".to_string()];
+        let config = TextBoxConfig::dos().with_caption("diff");
+
+        assert_eq!(
+            generate_split(&left, &right, config),
+            "\
+╔═══════════╗
+║ aaaa │ c  ║
+║ b    │ dd ║
+║      │ e  ║
+<diff>══════╝"
+        );
+        assert_eq!(
+            generate_split(&[], &[], config),
+            generate_with_config(&[], config)
+        );
+        assert_eq!(
+            Renderer::with_width(config, 10).render_split(&left, &right),
+            "\
+╔════════╗
+║ aaa │  ║
+║ b   │  ║
+║     │  ║
+<diff>═══╝"
+        );
+        assert_eq!(
+            Renderer::with_width(config, 5).render_split(&left, &right),
+            "╔═══╗\n║   ║\n║   ║\n║   ║\n<d>═╝"
+        );
+        assert_eq!(
+            Renderer::with_width(TextBoxConfig::ascii(), 6)
+                .render_split(&["ab".into()], &["c".into()]),
+            "+----+\n|  | |\n+----+"
+        );
+
+        let config = TextBoxConfig {
+            column_divider: "\x1b[2m|\x1b[0m",
+            ..TextBoxConfig::ascii().with_measurer(&AnsiWidth(CharWidth))
+        };
+        assert_eq!(
+            generate_split(&left[..1], &right[..1], config),
+            "+----------+\n| aaaa \x1b[2m|\x1b[0m c |\n+----------+"
+        );
+    }
+
+    #[test]
+    fn test_indent() {
+        let footer = || vec!["f".to_string()];
+        let config = TextBoxConfig::default()
+            .with_caption("C")
+            .with_footer(&footer)
+            .with_tail(1);
+        let lines = ["a".to_string()];
+
+        let plain = generate_with_config(&lines, config);
+        let indented = generate_with_config(&lines, config.with_indent(2));
+        assert_eq!(
+            indented,
+            plain
+                .lines()
+                .map(|line| format!("  {}", line))
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+
+        let decorated = generate_with_config(
+            &lines,
+            config.with_indent(1).with_row_decorators("> ", " <"),
+        );
+        assert_eq!(
+            decorated,
+            plain
+                .lines()
+                .map(|line| format!(" > {} <", line))
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_separated() {
+        let a = ["a".to_string()];
+        let b = ["b".to_string(), "c".to_string()];
+        let indices = std::sync::Mutex::new(Vec::new());
+        let hook = |index: usize, _: &str| {
+            indices.lock().unwrap().push(index);
+            LineStyle::new()
+        };
+        let config = TextBoxConfig::dos().with_line_style(&hook);
+
+        assert_eq!(
+            generate_separated(&[&[], &a, &[], &b], config),
+            "\
+╔═══╗
+║ a ║
+╠═══╣
+║ b ║
+║ c ║
+╚═══╝"
+        );
+        assert_eq!(*indices.lock().unwrap(), [0, 1, 2]);
+        assert_eq!(
+            generate_separated(&[], config),
+            generate_with_config(&[], config)
+        );
+    }
+
+    #[test]
+    fn test_generate_box_str() {
+        assert_eq!(
+            generate_box_str("a\r\nbc\n\n"),
+            generate_box(&["a".to_string(), "bc".to_string(), "".to_string()])
+        );
+        assert_eq!(generate_box_str(""), generate_box(&[]));
+    }
+
+    #[test]
+    fn test_generate_into() {
+        let lines = vec![String::from("a")];
+        let mut out = String::from("> ");
+        generate_into(&mut out, &lines, TextBoxConfig::ascii()).unwrap();
+        assert_eq!(out, "> +---+\n| a |\n+---+");
+
+        struct Report<'a>(&'a [String], TextBoxConfig<'a>);
+
+        impl fmt::Display for Report<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                generate_into(f, self.0, self.1)
+            }
+        }
+
+        let lines = (0..10).map(|n| n.to_string()).collect::<Vec<_>>();
+        for config in [
+            TextBoxConfig::ascii(),
+            TextBoxConfig::default().with_caption("c").with_tail(1),
+            TextBoxConfig::dos()
+                .with_line_ending("\r\n")
+                .trailing_newline(),
+        ] {
+            for lines in [&lines[..1], &lines[..]] {
+                let expected = generate_with_config(lines, config);
+                assert_eq!(Report(lines, config).to_string(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_generate_box_iter() {
+        let lines = vec![String::from("a"), String::from("bc")];
+        assert_eq!(generate_box_iter(&lines), generate_box(&lines));
+        assert_eq!(
+            generate_box_iter(lines.iter().map(String::as_str)),
+            generate_box(&lines)
+        );
+        assert_eq!(generate_box_iter(Vec::<&str>::new()), generate_box(&[]));
+        let config = TextBoxConfig::ascii().with_caption("c");
+        assert_eq!(
+            generate_with_config_iter(["a", "bc"], config),
+            generate_with_config(&lines, config)
+        );
+    }
+
+    #[test]
+    fn test_with_corners() {
+        let config = TextBoxConfig::ascii()
+            .with_caption("c")
+            .with_corners("/", "\\", "\\", "/")
+            .unwrap();
+        assert_eq!(
+            generate_with_config(&["abc".to_string()], config),
+            "/-----\\\n| abc |\n<c>---/"
+        );
+
+        assert!(TextBoxConfig::ascii()
+            .with_corners("", "+", "+", "+")
+            .is_none());
+        let measure = ByteWidth;
+        assert!(TextBoxConfig::ascii()
+            .with_measurer(&measure)
+            .with_corners("✦", "+", "+", "+")
+            .is_none());
+    }
+
+    #[test]
+    fn test_map_boxes() {
+        let config = TextBoxConfig::ascii();
+        let map = (0..20).map(|n| (n, n * 2)).collect::<HashMap<_, _>>();
+        let msg = generate_hash_map(&map, true, None, config);
+        let keys = msg.lines().filter_map(|row| row.split_whitespace().nth(1));
+        let mut expected = (0..20).map(|n| n.to_string()).collect::<Vec<_>>();
+        expected.sort();
+        assert_eq!(keys.collect::<Vec<_>>(), expected);
+
+        let unsorted = generate_hash_map(&map, false, None, config);
+        assert_eq!(unsorted.len(), msg.len());
+
+        let btree = map.into_iter().collect::<BTreeMap<_, _>>();
+        let msg = generate_btree_map(&btree, Some(" ->"), config);
+        assert_eq!(msg.lines().nth(1), Some("| 0  -> 0  |"));
+        assert_eq!(msg.lines().nth(20), Some("| 19 -> 38 |"));
+    }
 }
@@ -0,0 +1,96 @@
+//! The input lines carrying their own layout and style, see [`Renderer::render_lines`](crate::Renderer::render_lines).
+use std::borrow::Cow;
+
+use crate::{Alignment, Style};
+
+/// The number of spaces inserted for every indent level of a [`Line`].
+pub(crate) const INDENT_WIDTH: usize = 2;
+
+/// A line of text with the way it's displayed, rendered by [`Renderer::render_lines`](crate::Renderer::render_lines).
+/// The line settings take the place of the [`line_style`](crate::TextBoxConfig::line_style) hook.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// let lines = [
+///     Line::new("Services").with_alignment(Alignment::Center),
+///     Line::new("api: up").with_indent(1),
+///     Line::new("db: down").with_indent(1).highlighted(),
+/// ];
+/// let renderer = Renderer::new(TextBoxConfig::default().strip_styles());
+///
+/// assert_eq!(renderer.render_lines(&lines), "\
+/// ╭────────────╮
+/// │  Services  │
+/// │   api: up  │
+/// │   db: down │
+/// ╰────────────╯");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Line<'t> {
+    /// The text of the line.
+    pub text: Cow<'t, str>,
+    /// Overrides the alignment of the line within the box.
+    pub alignment: Option<Alignment>,
+    /// The indent level, every level is two spaces wide.
+    pub indent: usize,
+    /// Whether the line stands out by being displayed in bold.
+    pub highlight: bool,
+    /// The style of the line. Indent and padding are never styled.
+    pub style: Style,
+}
+
+impl<'t> Line<'t> {
+    /// Create a line displaying the text as is.
+    pub fn new(text: impl Into<Cow<'t, str>>) -> Self {
+        Self {
+            text: text.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Align the line in the given way.
+    pub fn with_alignment(self, alignment: Alignment) -> Self {
+        Self {
+            alignment: Some(alignment),
+            ..self
+        }
+    }
+
+    /// Indent the line by the given number of levels.
+    pub fn with_indent(self, indent: usize) -> Self {
+        Self { indent, ..self }
+    }
+
+    /// Make the line stand out.
+    pub fn highlighted(self) -> Self {
+        Self {
+            highlight: true,
+            ..self
+        }
+    }
+
+    /// Display the line in the given terminal style.
+    pub fn with_style(self, style: Style) -> Self {
+        Self { style, ..self }
+    }
+
+    /// The style the text is displayed in, including the highlight.
+    pub(crate) fn text_style(&self) -> Style {
+        Style {
+            bold: self.style.bold || self.highlight,
+            ..self.style
+        }
+    }
+}
+
+impl<'t> From<&'t str> for Line<'t> {
+    fn from(text: &'t str) -> Self {
+        Self::new(text)
+    }
+}
+
+impl From<String> for Line<'_> {
+    fn from(text: String) -> Self {
+        Self::new(text)
+    }
+}
@@ -0,0 +1,183 @@
+//! The inline markup converted to styled spans, see [`StyledLine::from_markup`].
+use crate::{ansi, style, Color, Style, StyledLine};
+
+/// Parse the given markup into a styled line.
+///
+/// - `**text**` makes the text bold.
+/// - `_text_` dims the text. The underscores inside words, like in `snake_case`, are kept.
+/// - `[red]text[/red]` colors the text, `[/]` closes the last color as well.
+/// - A backslash displays the next character as is.
+///
+/// Unknown tags are displayed as is. The escape sequences are copied whole, and the SGR ones
+/// restyle the text that follows them, like in [`StyledLine::from_ansi`].
+pub(crate) fn parse(markup: &str) -> StyledLine<'static> {
+    let mut parser = Parser {
+        line: StyledLine::new(),
+        text: String::new(),
+        style: Style::new(),
+        colors: Vec::new(),
+    };
+
+    let mut chars = markup.char_indices().peekable();
+    let mut previous = None;
+    while let Some((index, c)) = chars.next() {
+        let rest = &markup[index..];
+        match c {
+            '\x1b' => {
+                let escape = &rest[..ansi::escape_len(rest)];
+                if ansi::is_style(escape) {
+                    let params = &escape[2..escape.len() - 1];
+                    parser.restyle(style::apply_sgr(parser.style, params));
+                } else {
+                    parser.text.push_str(escape);
+                }
+                // Skip the rest of the sequence; `ESC` has already been consumed.
+                for _ in 1..escape.chars().count() {
+                    chars.next();
+                }
+            }
+            '\\' => match chars.next() {
+                Some((_, escaped)) => parser.text.push(escaped),
+                None => parser.text.push('\\'),
+            },
+            '*' if rest.starts_with("**") => {
+                chars.next();
+                let bold = !parser.style.bold;
+                parser.restyle(Style {
+                    bold,
+                    ..parser.style
+                });
+            }
+            '_' if is_underscore_toggle(
+                previous,
+                chars.peek().map(|&(_, c)| c),
+                parser.style.dim,
+            ) =>
+            {
+                let dim = !parser.style.dim;
+                parser.restyle(Style {
+                    dim,
+                    ..parser.style
+                });
+            }
+            '[' => match parse_tag(rest) {
+                Some((tag, len)) if parser.apply_tag(tag) => {
+                    // Skip the rest of the tag; `[` has already been consumed.
+                    for _ in 1..len {
+                        chars.next();
+                    }
+                }
+                _ => parser.text.push('['),
+            },
+            c => parser.text.push(c),
+        }
+        previous = Some(c);
+    }
+
+    parser.flush();
+    parser.line
+}
+
+/// The state of the markup parser.
+struct Parser {
+    line: StyledLine<'static>,
+    /// The text of the current span.
+    text: String,
+    style: Style,
+    /// The stack of the open color tags.
+    colors: Vec<Color>,
+}
+
+impl Parser {
+    /// Finish the current span.
+    fn flush(&mut self) {
+        if !self.text.is_empty() {
+            let text = std::mem::take(&mut self.text);
+            self.line.push(text, self.style);
+        }
+    }
+
+    /// Finish the current span and start a new one in the given style.
+    fn restyle(&mut self, style: Style) {
+        self.flush();
+        self.style = style;
+    }
+
+    /// Apply the given color tag, returning whether it's known.
+    fn apply_tag(&mut self, tag: &str) -> bool {
+        if let Some(closing) = tag.strip_prefix('/') {
+            let matches = match (closing, self.colors.last()) {
+                (_, None) => false,
+                ("", Some(_)) => true,
+                (name, Some(&color)) => Color::from_name(name) == Some(color),
+            };
+            if matches {
+                self.colors.pop();
+                let fg = self.colors.last().copied();
+                self.restyle(Style { fg, ..self.style });
+            }
+            return matches;
+        }
+
+        match Color::from_name(tag) {
+            Some(color) => {
+                self.colors.push(color);
+                self.restyle(self.style.fg(color));
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Whether an underscore between the given characters opens or closes the dimmed text.
+fn is_underscore_toggle(previous: Option<char>, next: Option<char>, dim: bool) -> bool {
+    let is_word = |c: Option<char>| c.is_some_and(char::is_alphanumeric);
+    if dim {
+        !is_word(next)
+    } else {
+        !is_word(previous)
+    }
+}
+
+/// Parse the tag at the start of the text, returning its name and its length in characters.
+fn parse_tag(text: &str) -> Option<(&str, usize)> {
+    let end = text.find(']')?;
+    let tag = &text[1..end];
+    if tag.contains('[') {
+        return None;
+    }
+    Some((tag, tag.chars().count() + 2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markup() {
+        let line =
+            parse(r"**Disk** [red]94%[/red] of _snake_case_ \**, [blue]a[green]b[/]c[/blue] [x]");
+
+        let spans = line
+            .spans
+            .iter()
+            .map(|span| (&*span.text, span.style))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            spans,
+            [
+                ("Disk", Style::new().bold()),
+                (" ", Style::new()),
+                ("94%", Style::new().fg(Color::Red)),
+                (" of ", Style::new()),
+                ("snake_case", Style::new().dim()),
+                (" **, ", Style::new()),
+                ("a", Style::new().fg(Color::Blue)),
+                ("b", Style::new().fg(Color::Green)),
+                ("c", Style::new().fg(Color::Blue)),
+                (" [x]", Style::new()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,298 @@
+//! The pluggable measurement of the text width used by the layout engine.
+use std::borrow::Cow;
+
+use crate::{ansi, grapheme, spacer::push_spaces, RESET};
+
+/// The way the width of the text is measured in columns, see [`TextBoxConfig::with_measurer`](crate::TextBoxConfig::with_measurer).
+///
+/// The layout engine measures the lines, the captions, and the footers with it,
+/// and truncates them with it when they don't fit into a box with a fixed width.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// /// Every character takes a column, except for the zero width spaces.
+/// struct SkipZeroWidth;
+///
+/// impl WidthMeasurer for SkipZeroWidth {
+///     fn width(&self, text: &str) -> usize {
+///         text.chars().filter(|&c| c != '\u{200b}').count()
+///     }
+/// }
+///
+/// let lines = [String::from("a\u{200b}b")];
+/// let msg = generate_with_config(&lines, TextBoxConfig::ascii().with_measurer(&SkipZeroWidth));
+/// assert_eq!(msg, "+----+\n| a\u{200b}b |\n+----+");
+/// ```
+pub trait WidthMeasurer {
+    /// The number of columns taken by the text.
+    fn width(&self, text: &str) -> usize;
+
+    /// The longest prefix of the text that is at most `max_width` columns wide.
+    /// By default, the characters are measured one by one, and the text is never cut
+    /// within a grapheme cluster, like a letter with its combining accent.
+    fn truncate<'t>(&self, text: &'t str, max_width: usize) -> &'t str {
+        let mut width = 0;
+        for (index, c) in text.char_indices() {
+            width += self.width(c.encode_utf8(&mut [0; 4]));
+            if width > max_width {
+                return &text[..grapheme::floor_boundary(text, index)];
+            }
+        }
+        text
+    }
+}
+
+/// Every byte takes a column, which is right for the ASCII text. This is the default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ByteWidth;
+
+impl WidthMeasurer for ByteWidth {
+    fn width(&self, text: &str) -> usize {
+        text.len()
+    }
+
+    fn truncate<'t>(&self, text: &'t str, max_width: usize) -> &'t str {
+        if text.len() <= max_width {
+            return text;
+        }
+        let mut end = max_width;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        &text[..grapheme::floor_boundary(text, end)]
+    }
+}
+
+/// Every character takes a column.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CharWidth;
+
+impl WidthMeasurer for CharWidth {
+    fn width(&self, text: &str) -> usize {
+        text.chars().count()
+    }
+
+    fn truncate<'t>(&self, text: &'t str, max_width: usize) -> &'t str {
+        if text.len() <= max_width {
+            return text;
+        }
+        if text.as_bytes()[..=max_width].is_ascii() {
+            return &text[..grapheme::floor_boundary(text, max_width)];
+        }
+        match text.char_indices().nth(max_width) {
+            Some((end, _)) => &text[..grapheme::floor_boundary(text, end)],
+            None => text,
+        }
+    }
+}
+
+/// The characters take as many columns as they do in the terminals: zero for the combining
+/// characters and two for the wide East Asian characters and most emoji.
+/// Enabled by the `unicode-width` feature.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// let lines = [String::from("日本")];
+/// let msg = generate_with_config(&lines, TextBoxConfig::ascii().with_measurer(&UnicodeWidth));
+/// assert_eq!(msg, "+------+\n| 日本 |\n+------+");
+/// ```
+#[cfg(feature = "unicode-width")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UnicodeWidth;
+
+#[cfg(feature = "unicode-width")]
+impl WidthMeasurer for UnicodeWidth {
+    fn width(&self, text: &str) -> usize {
+        unicode_width::UnicodeWidthStr::width(text)
+    }
+}
+
+/// Skips the ANSI escape sequences, measuring the rest of the text with the inner measurer.
+/// The escape sequences before the cut are kept when the text is truncated.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// let lines = [String::from("\x1b[1mok\x1b[0m")];
+/// let msg = generate_with_config(&lines, TextBoxConfig::ascii().with_measurer(&AnsiWidth(CharWidth)));
+/// assert_eq!(msg, "+----+\n| \x1b[1mok\x1b[0m |\n+----+");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AnsiWidth<M>(pub M);
+
+impl<M: WidthMeasurer> WidthMeasurer for AnsiWidth<M> {
+    fn width(&self, text: &str) -> usize {
+        ansi::visible_runs(text)
+            .map(|(_, run)| self.0.width(run))
+            .sum()
+    }
+
+    fn truncate<'t>(&self, text: &'t str, max_width: usize) -> &'t str {
+        let mut remaining = max_width;
+        for (start, run) in ansi::visible_runs(text) {
+            let width = self.0.width(run);
+            if width > remaining {
+                let end = start + self.0.truncate(run, remaining).len();
+                return &text[..end];
+            }
+            remaining -= width;
+        }
+        text
+    }
+}
+
+/// The measurer shared by the layout engine.
+pub(crate) type Measure<'m> = &'m (dyn WidthMeasurer + Sync);
+
+impl<M: WidthMeasurer + ?Sized> WidthMeasurer for &M {
+    fn width(&self, text: &str) -> usize {
+        (**self).width(text)
+    }
+
+    fn truncate<'t>(&self, text: &'t str, max_width: usize) -> &'t str {
+        (**self).truncate(text, max_width)
+    }
+}
+
+/// Truncate the given text to at most `max_width` columns, see [`WidthMeasurer::truncate`].
+/// If the measurer skips the escape sequences, the styles that are left active are closed,
+/// see [`truncate_styled`](crate::truncate_styled).
+pub(crate) fn truncate<'t, M: WidthMeasurer + ?Sized>(
+    measure: &M,
+    text: Cow<'t, str>,
+    max_width: usize,
+) -> Cow<'t, str> {
+    if ansi::find_escape(&text).is_some() && measure.width(RESET) == 0 {
+        return match text {
+            Cow::Borrowed(text) => ansi::truncate_styled(text, max_width, measure),
+            Cow::Owned(text) => ansi::truncate_styled(&text, max_width, measure)
+                .into_owned()
+                .into(),
+        };
+    }
+    match text {
+        Cow::Borrowed(text) => Cow::Borrowed(measure.truncate(text, max_width)),
+        Cow::Owned(mut text) => {
+            let len = measure.truncate(&text, max_width).len();
+            text.truncate(len);
+            Cow::Owned(text)
+        }
+    }
+}
+
+/// Truncate the given text to at most `max_width` columns like [`truncate`],
+/// ending it with the ellipsis, if given, when it doesn't fit.
+pub(crate) fn truncate_with<'t, M: WidthMeasurer + ?Sized>(
+    measure: &M,
+    text: Cow<'t, str>,
+    max_width: usize,
+    ellipsis: Option<&str>,
+) -> Cow<'t, str> {
+    match ellipsis {
+        Some(ellipsis) if measure.width(&text) > max_width => {
+            truncate_visible(&text, max_width, ellipsis, measure)
+                .into_owned()
+                .into()
+        }
+        _ => truncate(measure, text, max_width),
+    }
+}
+
+/// Replace the tabs of the text with the spaces up to the next multiple of `tab_width`
+/// columns, measuring the text before every tab with the measurer.
+pub(crate) fn expand_tabs<M: WidthMeasurer + ?Sized>(
+    measure: &M,
+    text: &str,
+    tab_width: usize,
+) -> String {
+    let mut expanded = String::with_capacity(text.len());
+    for (index, part) in text.split('\t').enumerate() {
+        if index > 0 && tab_width > 0 {
+            let column = measure.width(&expanded);
+            push_spaces(&mut expanded, tab_width - column % tab_width);
+        }
+        expanded.push_str(part);
+    }
+    expanded
+}
+
+/// Truncate the text to at most `max_width` columns with the same width semantics as the
+/// lines that don't fit into a box with a fixed width, replacing the end of the text with
+/// the given ellipsis if it doesn't fit. The text that fits is returned as is.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// assert_eq!(truncate_visible("disk full", 7, "...", &CharWidth), "disk...");
+/// assert_eq!(truncate_visible("disk", 7, "...", &CharWidth), "disk");
+/// assert_eq!(truncate_visible("\x1b[1mdisk full\x1b[0m", 5, "…", &AnsiWidth(CharWidth)), "\x1b[1mdisk\x1b[0m…");
+/// ```
+pub fn truncate_visible<'t, M: WidthMeasurer + ?Sized>(
+    text: &'t str,
+    max_width: usize,
+    ellipsis: &str,
+    measurer: &M,
+) -> Cow<'t, str> {
+    if measurer.width(text) <= max_width {
+        return Cow::Borrowed(text);
+    }
+
+    let ellipsis = measurer.truncate(ellipsis, max_width);
+    let width = max_width - measurer.width(ellipsis);
+    let mut text = truncate(measurer, Cow::Borrowed(text), width).into_owned();
+    text.push_str(ellipsis);
+    Cow::Owned(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(ByteWidth.truncate("aé", 2), "a");
+        assert_eq!(CharWidth.truncate("aéb", 2), "aé");
+        assert_eq!(CharWidth.truncate("ab", 5), "ab");
+
+        let ansi = AnsiWidth(ByteWidth);
+        assert_eq!(ansi.width("\x1b[31mab\x1b[0mc"), 3);
+        assert_eq!(ansi.truncate("\x1b[31mab\x1b[0mc", 2), "\x1b[31mab\x1b[0m");
+        assert_eq!(ansi.truncate("\x1b[31mab\x1b[0mc", 1), "\x1b[31ma");
+        assert_eq!(ansi.width("a\x1b[3"), 1);
+    }
+
+    #[test]
+    fn test_truncate_visible() {
+        assert_eq!(truncate_visible("abcdef", 4, "..", &ByteWidth), "ab..");
+        assert_eq!(truncate_visible("abcdef", 1, "..", &ByteWidth), ".");
+        assert_eq!(truncate_visible("abcdef", 0, "..", &ByteWidth), "");
+        assert_eq!(truncate_visible("abcdef", 3, "", &ByteWidth), "abc");
+        assert_eq!(truncate_visible("héllo", 4, "~", &CharWidth), "hél~");
+        assert!(matches!(
+            truncate_visible("abc", 3, "..", &ByteWidth),
+            Cow::Borrowed(_)
+        ));
+    }
+
+    #[cfg(feature = "unicode-width")]
+    #[test]
+    fn test_unicode_width() {
+        assert_eq!(UnicodeWidth.width("日本"), 4);
+        assert_eq!(UnicodeWidth.width("e\u{301}"), 1);
+        assert_eq!(UnicodeWidth.truncate("日本語", 5), "日本");
+        assert_eq!(UnicodeWidth.truncate("e\u{301}x", 1), "e\u{301}");
+
+        let config = crate::TextBoxConfig::ascii().unicode_width();
+        let renderer = crate::Renderer::with_width(config, 9);
+        assert_eq!(
+            renderer.render(&["日本語です".to_string()]),
+            "+-------+\n| 日本  |\n+-------+"
+        );
+    }
+
+    #[test]
+    fn test_expand_tabs() {
+        assert_eq!(expand_tabs(&ByteWidth, "a\tbc\td", 4), "a   bc  d");
+        assert_eq!(expand_tabs(&ByteWidth, "\t\tabcd\t", 4), "        abcd    ");
+        assert_eq!(expand_tabs(&CharWidth, "é\tb", 2), "é b");
+        assert_eq!(expand_tabs(&ByteWidth, "a\tb", 0), "ab");
+    }
+}
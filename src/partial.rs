@@ -0,0 +1,152 @@
+//! The partial configs overriding some of the fields of a [`TextBoxConfig`].
+use std::fmt;
+
+use crate::{
+    Alignment, Border, Charset, ControlPolicy, Highlighter, LineStyleHook, Orientation,
+    OverflowPolicy, PostRenderHook, Side, TextBoxConfig, WidthMeasurer,
+};
+
+/// Define [`PartialConfig`] with an optional override for every listed field of the config.
+macro_rules! partial_config {
+    ($($field:ident: $ty:ty,)*) => {
+        /// A set of overrides for the fields of a [`TextBoxConfig`], see [`TextBoxConfig::merge`].
+        /// The fields that are `None` fall through to the config they're merged into.
+        #[derive(Clone, Copy, Default)]
+        pub struct PartialConfig<'a> {
+            $(
+                #[doc = concat!("Overrides [`TextBoxConfig::", stringify!($field), "`].")]
+                pub $field: Option<$ty>,
+            )*
+        }
+
+        impl<'a> PartialConfig<'a> {
+            /// Layer the given overrides on top of these ones.
+            pub fn merge(self, overrides: PartialConfig<'a>) -> Self {
+                Self {
+                    $($field: overrides.$field.or(self.$field),)*
+                }
+            }
+        }
+
+        impl<'a> TextBoxConfig<'a> {
+            /// Override the fields of the config that are set in the partial config,
+            /// so the themes of an application can be layered on top of the presets.
+            ///
+            /// ```
+            /// # use plain_msgbox::*;
+            /// let theme = PartialConfig {
+            ///     indent: Some(2),
+            ///     last_line_caption: Some(Some("app")),
+            ///     ..PartialConfig::default()
+            /// };
+            /// let user = PartialConfig {
+            ///     last_line_caption: Some(None),
+            ///     ..PartialConfig::default()
+            /// };
+            /// let config = TextBoxConfig::ascii().merge(theme.merge(user));
+            ///
+            /// assert_eq!(generate_with_config(&[String::from("ok")], config), "  \
+            /// +----+
+            ///   | ok |
+            ///   +----+");
+            /// ```
+            pub fn merge(self, overrides: PartialConfig<'a>) -> Self {
+                Self {
+                    $($field: overrides.$field.unwrap_or(self.$field),)*
+                }
+            }
+        }
+
+        impl fmt::Debug for PartialConfig<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let mut debug = f.debug_struct("PartialConfig");
+                $(
+                    if self.$field.is_some() {
+                        debug.field(stringify!($field), &"..");
+                    }
+                )*
+                debug.finish_non_exhaustive()
+            }
+        }
+    };
+}
+
+partial_config! {
+    horizontal_bar: &'a str,
+    vertical_bar: &'a str,
+    left_top_corner: &'a str,
+    left_bottom_corner: &'a str,
+    right_top_corner: &'a str,
+    right_bottom_corner: &'a str,
+    left_tee: &'a str,
+    right_tee: &'a str,
+    tail: &'a str,
+    last_line_caption: Option<&'a str>,
+    timestamp: Option<&'a (dyn Fn() -> String + Sync)>,
+    footer: Option<&'a (dyn Fn() -> Vec<String> + Sync)>,
+    tail_column: Option<usize>,
+    mirrored: bool,
+    side_caption: Option<&'a str>,
+    side_caption_border: Side,
+    orientation: Orientation,
+    line_style: Option<&'a LineStyleHook<'a>>,
+    post_render: Option<&'a PostRenderHook<'a>>,
+    markup: bool,
+    ragged: bool,
+    trim_trailing_whitespace: bool,
+    collapse_blank_lines: bool,
+    trim_blank_lines: bool,
+    line_spacing: usize,
+    column_divider: &'a str,
+    align_numbers: bool,
+    indent: usize,
+    row_prefix: &'a str,
+    row_suffix: &'a str,
+    strip_styles: bool,
+    border: Option<&'a (dyn Border + Sync)>,
+    measurer: Option<&'a (dyn WidthMeasurer + Sync)>,
+    overflow: OverflowPolicy,
+    truncate_caption: bool,
+    padding_fill: Option<&'a str>,
+    charset: Charset,
+    expand_sections: bool,
+    trim_internal_frames: bool,
+    dedupe_lines: bool,
+    line_numbers: bool,
+    highlighter: Option<&'a (dyn Highlighter + Sync)>,
+    max_width: Option<usize>,
+    ellipsis: Option<&'a str>,
+    width: Option<usize>,
+    min_width: Option<usize>,
+    alignment: Alignment,
+    vertical_padding: usize,
+    caption_alignment: Alignment,
+    tab_width: Option<usize>,
+    control_chars: ControlPolicy,
+    line_ending: &'a str,
+    trailing_newline: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge() {
+        let overrides = PartialConfig {
+            horizontal_bar: Some("="),
+            mirrored: Some(true),
+            ..PartialConfig::default()
+        };
+        let config = TextBoxConfig::default().with_indent(1).merge(overrides);
+        assert_eq!(config.horizontal_bar, "=");
+        assert!(config.mirrored);
+        assert_eq!(config.indent, 1);
+        assert_eq!(config.vertical_bar, TextBoxConfig::default().vertical_bar);
+
+        assert_eq!(
+            format!("{:?}", overrides),
+            r#"PartialConfig { horizontal_bar: "..", mirrored: "..", .. }"#
+        );
+    }
+}
@@ -0,0 +1,214 @@
+//! The building blocks of the boxes for composing custom layouts.
+use std::{borrow::Cow, vec};
+
+use crate::{
+    renderer::{push_bottom, push_top, render_content, trim_input, Row},
+    Alignment, TextBoxConfig,
+};
+
+/// Render a single content row of a box with the given content width, borders excluded:
+/// the line is padded, or truncated if it doesn't fit, and surrounded with the vertical bars.
+/// The rows rendered with the same width line up with the rest of the box.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// let config = TextBoxConfig::default();
+/// assert_eq!(render_line("ok", 6, &config), "│ ok     │");
+/// assert_eq!(render_line("truncated", 6, &config), "│ trunca │");
+/// ```
+pub fn render_line(line: &str, inner_width: usize, config: &TextBoxConfig<'_>) -> String {
+    let config = &config.charset.apply(*config);
+    let measure = config.measure();
+    let text = measure.truncate(trim_input(config, line), inner_width);
+    let alignment = if config.mirrored {
+        Alignment::Right
+    } else {
+        Alignment::Left
+    };
+    render_content(
+        config,
+        (text, measure.width(text)),
+        inner_width,
+        alignment,
+        None,
+    )
+}
+
+/// Render the top border of a box with the given content width, see [`render_line`].
+///
+/// ```
+/// # use plain_msgbox::*;
+/// assert_eq!(top_border(4, &TextBoxConfig::default()), "╭──────╮");
+/// ```
+pub fn top_border(inner_width: usize, config: &TextBoxConfig<'_>) -> String {
+    let mut top = String::new();
+    push_top(&mut top, &config.charset.apply(*config), inner_width, None);
+    top
+}
+
+/// Render the bottom border of a box with the given content width, see [`render_line`].
+/// The caption replaces the corner like the [`last_line_caption`](TextBoxConfig::last_line_caption)
+/// of the config does, and is truncated if it doesn't fit into the box.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// let config = TextBoxConfig::default();
+/// assert_eq!(bottom_border(4, &config, None), "╰──────╯");
+/// assert_eq!(bottom_border(4, &config, Some("Done")), "<Done>─╯");
+/// assert_eq!(bottom_border(4, &config.mirrored(), Some("Done")), "╰─<Done>");
+/// ```
+pub fn bottom_border(
+    inner_width: usize,
+    config: &TextBoxConfig<'_>,
+    caption: Option<&str>,
+) -> String {
+    let config = &config.charset.apply(*config);
+    let measure = config.measure();
+    let caption = caption.map(|caption| measure.truncate(caption, inner_width));
+    let mut bottom = String::new();
+    push_bottom(&mut bottom, config, measure, inner_width, caption, None);
+    bottom
+}
+
+/// A structural part of a box, see [`BoxParts`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BoxPart<'l> {
+    /// The top border.
+    TopBorder,
+    /// A content row with the text of the line, including the escape sequences of its styles.
+    Content(Cow<'l, str>),
+    /// A horizontal rule between the content rows.
+    Separator,
+    /// A horizontal rule between the content rows with the title embedded.
+    Divider(Cow<'l, str>),
+    /// The bottom border with the caption, truncated if the box has a fixed width.
+    BottomBorder { caption: Option<String> },
+}
+
+/// An iterator over the structural parts of a box, from the top border to the bottom border,
+/// for mapping them to other representations like the HTML elements.
+/// Created by [`Renderer::parts`](crate::Renderer::parts).
+#[derive(Debug)]
+pub struct BoxParts<'l> {
+    inner_width: usize,
+    /// The caption of the bottom border, taken once it's yielded.
+    caption: Option<String>,
+    rows: vec::IntoIter<Row<'l>>,
+    /// Whether the top border has been yielded.
+    started: bool,
+    /// Whether the bottom border has been yielded.
+    finished: bool,
+}
+
+impl<'l> BoxParts<'l> {
+    pub(crate) fn new(inner_width: usize, caption: Option<String>, rows: Vec<Row<'l>>) -> Self {
+        Self {
+            inner_width,
+            caption,
+            rows: rows.into_iter(),
+            started: false,
+            finished: false,
+        }
+    }
+
+    /// The width of the content area of the box, see [`render_line`].
+    pub fn inner_width(&self) -> usize {
+        self.inner_width
+    }
+}
+
+impl<'l> Iterator for BoxParts<'l> {
+    type Item = BoxPart<'l>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.started {
+            self.started = true;
+            return Some(BoxPart::TopBorder);
+        }
+        match self.rows.next() {
+            Some(Row::Content { text, .. }) => Some(BoxPart::Content(text)),
+            Some(Row::Rule) => Some(BoxPart::Separator),
+            Some(Row::Divider { title, .. }) => Some(BoxPart::Divider(title)),
+            None if self.finished => None,
+            None => {
+                self.finished = true;
+                Some(BoxPart::BottomBorder {
+                    caption: self.caption.take(),
+                })
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.rows.len() + !self.started as usize + !self.finished as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for BoxParts<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_line() {
+        let config = TextBoxConfig::dos().mirrored();
+        assert_eq!(render_line("ab", 4, &config), "║   ab ║");
+        assert_eq!(render_line("", 0, &config), "║  ║");
+
+        let config = TextBoxConfig::default().ragged();
+        assert_eq!(render_line("ab", 4, &config), "│ ab");
+    }
+
+    #[test]
+    fn test_borders_match_box() {
+        let config = TextBoxConfig::dos().with_caption("Caption");
+        let lines = [String::from("a"), String::from("bc")];
+        let manual = [
+            top_border(7, &config),
+            render_line("a", 7, &config),
+            render_line("bc", 7, &config),
+            bottom_border(7, &config, config.last_line_caption),
+        ];
+        assert_eq!(
+            manual.join("\n"),
+            crate::generate_with_config(&lines, config)
+        );
+
+        assert_eq!(bottom_border(2, &config, Some("Caption")), "<Ca>═╝");
+    }
+
+    #[test]
+    fn test_parts() {
+        let renderer =
+            crate::Renderer::with_width(TextBoxConfig::default().with_caption("Long"), 6);
+        let lines = [
+            String::from("a"),
+            String::new(),
+            String::new(),
+            String::from("bcd"),
+        ];
+        let parts = renderer.parts(&lines);
+        assert_eq!(parts.inner_width(), 2);
+        assert_eq!(parts.len(), 6);
+        assert_eq!(
+            parts.collect::<Vec<_>>(),
+            [
+                BoxPart::TopBorder,
+                BoxPart::Content("a".into()),
+                BoxPart::Content("".into()),
+                BoxPart::Content("".into()),
+                BoxPart::Content("bc".into()),
+                BoxPart::BottomBorder {
+                    caption: Some("Lo".into())
+                },
+            ]
+        );
+
+        let renderer = crate::Renderer::new(TextBoxConfig::default().collapse_blank_lines());
+        let parts = renderer.parts(&lines).collect::<Vec<_>>();
+        assert_eq!(parts[2], BoxPart::Content("".into()));
+        assert_eq!(parts[3], BoxPart::Content("bcd".into()));
+    }
+}
@@ -0,0 +1,77 @@
+//! The laid out boxes that can be inspected and changed before they're rendered.
+use std::borrow::Cow;
+
+use crate::{
+    renderer::{Row, BORDER_WIDTH},
+    Renderer,
+};
+
+/// The rows of a message box laid out by [`Renderer::plan`], before they're turned into a string.
+///
+/// The rows only hold the content between the top and the bottom borders, which are
+/// computed when the plan is rendered, so the rows can be freely inserted, removed or edited.
+/// Unless the renderer has a fixed width, the box is widened to fit the longest row.
+#[derive(Debug, Clone)]
+pub struct RenderPlan<'r, 'a> {
+    renderer: &'r Renderer<'a>,
+    /// The rows between the top and the bottom borders, including the timestamp and the footer.
+    pub rows: Vec<Row<'static>>,
+}
+
+impl<'r, 'a> RenderPlan<'r, 'a> {
+    pub(crate) fn new(renderer: &'r Renderer<'a>, rows: Vec<Row<'static>>) -> Self {
+        Self { renderer, rows }
+    }
+
+    /// Create a content row measured and aligned like the input lines,
+    /// truncated to the fixed width of the renderer if it has one.
+    pub fn line(&self, text: impl Into<String>) -> Row<'static> {
+        let text = Cow::Owned(text.into());
+        self.renderer
+            .content_row(text, self.renderer.default_alignment())
+    }
+
+    /// The width of the rendered box, borders included.
+    pub fn width(&self) -> usize {
+        self.renderer.borders(&self.rows).inner_width + BORDER_WIDTH
+    }
+
+    /// Render the rows between the borders of the box.
+    pub fn render(&self) -> String {
+        self.renderer.render_layout(&self.rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Alignment, TextBoxConfig};
+
+    #[test]
+    fn test_plan_matches_render() {
+        let config = TextBoxConfig::default().with_caption("Caption");
+        let lines = [String::from("a"), String::new(), String::from("bc")];
+        let renderer = Renderer::new(config.collapse_blank_lines());
+
+        assert_eq!(renderer.plan(&lines).render(), renderer.render(&lines));
+    }
+
+    #[test]
+    fn test_fixed_width_plan() {
+        let renderer = Renderer::with_width(TextBoxConfig::default(), 8);
+        let mut plan = renderer.plan(&[String::from("a")]);
+        plan.rows.push(plan.line("truncated"));
+        plan.rows.push(Row::blank(Alignment::Right));
+
+        assert_eq!(plan.width(), 8);
+        assert_eq!(
+            plan.render(),
+            "\
+╭──────╮
+│ a    │
+│ trun │
+│      │
+╰──────╯"
+        );
+    }
+}
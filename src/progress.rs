@@ -0,0 +1,42 @@
+//! Printing the boxes above the `indicatif` progress bars, enabled by the `indicatif` feature.
+use std::io;
+
+use indicatif::{MultiProgress, ProgressBar};
+
+use crate::{generate_with_config, TextBoxConfig};
+
+/// Print a message box above the given progress bar without tearing it.
+/// Like [`ProgressBar::println`], prints nothing if the bar is hidden.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// use indicatif::ProgressBar;
+///
+/// let bar = ProgressBar::new(100);
+/// println_box(&bar, &[String::from("Downloaded 1 file")], TextBoxConfig::default());
+/// bar.finish();
+/// ```
+pub fn println_box(bar: &ProgressBar, lines: &[String], config: TextBoxConfig<'_>) {
+    bar.println(generate_with_config(lines, config));
+}
+
+/// Print a message box above the given group of progress bars without tearing them.
+/// See [`MultiProgress::println`].
+///
+/// ```
+/// # use plain_msgbox::*;
+/// use indicatif::{MultiProgress, ProgressBar};
+///
+/// let bars = MultiProgress::new();
+/// let bar = bars.add(ProgressBar::new(100));
+/// multi_println_box(&bars, &[String::from("Started 2 jobs")], TextBoxConfig::default())?;
+/// bar.finish();
+/// # std::io::Result::Ok(())
+/// ```
+pub fn multi_println_box(
+    bars: &MultiProgress,
+    lines: &[String],
+    config: TextBoxConfig<'_>,
+) -> io::Result<()> {
+    bars.println(generate_with_config(lines, config))
+}
@@ -0,0 +1,68 @@
+//! Python bindings built with PyO3, enabled by the `python` feature.
+//! Build the extension module with `maturin build --features python,pyo3/extension-module`.
+//!
+//! ```python
+//! import plain_msgbox
+//!
+//! print(plain_msgbox.generate_box(["a", "b"], caption="Config", dos=True))
+//! ```
+use pyo3::prelude::*;
+
+use crate::{Renderer, TextBoxConfig};
+
+/// The config with the given caption and style.
+fn config(caption: Option<&str>, dos: bool) -> TextBoxConfig<'_> {
+    let config = if dos {
+        TextBoxConfig::dos()
+    } else {
+        TextBoxConfig::default()
+    };
+    TextBoxConfig {
+        last_line_caption: caption,
+        ..config
+    }
+}
+
+/// Generate a message box displaying the given lines, optionally `width` columns wide.
+#[pyfunction]
+#[pyo3(signature = (lines, caption = None, dos = false, width = None))]
+fn generate_box(
+    lines: Vec<String>,
+    caption: Option<&str>,
+    dos: bool,
+    width: Option<usize>,
+) -> String {
+    let config = config(caption, dos);
+    match width {
+        Some(width) => Renderer::with_width(config, width).render(&lines),
+        None => Renderer::new(config).render(&lines),
+    }
+}
+
+/// Generate a message box displaying two sets of lines side by side.
+#[pyfunction]
+#[pyo3(signature = (left, right, caption = None, dos = false))]
+fn generate_split(
+    left: Vec<String>,
+    right: Vec<String>,
+    caption: Option<&str>,
+    dos: bool,
+) -> String {
+    crate::generate_split(&left, &right, config(caption, dos))
+}
+
+/// Generate a message box flowing the given items into columns.
+#[pyfunction]
+#[pyo3(signature = (items, width, caption = None, dos = false))]
+fn generate_flow(items: Vec<String>, width: usize, caption: Option<&str>, dos: bool) -> String {
+    crate::generate_flow(&items, width, config(caption, dos))
+}
+
+/// The `plain_msgbox` Python module.
+#[pymodule]
+fn plain_msgbox(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_function(wrap_pyfunction!(generate_box, module)?)?;
+    module.add_function(wrap_pyfunction!(generate_split, module)?)?;
+    module.add_function(wrap_pyfunction!(generate_flow, module)?)?;
+    Ok(())
+}
@@ -0,0 +1,2816 @@
+//! The layout engine shared by all of the `generate_*` functions.
+use std::{borrow::Cow, fmt, io, iter};
+
+use crate::{
+    ansi,
+    border::{glyphs, push_border, push_marked_border},
+    charset::{content_glyph, glyph},
+    grapheme, highlight, json, kv,
+    line::INDENT_WIDTH,
+    measure::{self, expand_tabs, Measure},
+    spacer::{push_pattern, push_spaces},
+    template::fill,
+    wrap::wrap,
+    Alignment, BorderRow, BoxParts, BoxStream, CaptionOverflow, ControlPolicy, KeyValue, Line,
+    LineSource, Orientation, OverflowPolicy, RenderPlan, RowKind, Section, Side, Span, Style,
+    StyledLine, TextBoxConfig, RESET,
+};
+
+/// The string placed between the fields returned by [`TextBoxConfig::footer`].
+const FOOTER_FIELD_SEPARATOR: &str = " | ";
+
+/// The number of columns taken by the vertical bars and the spaces around the content.
+pub(crate) const BORDER_WIDTH: usize = 4;
+/// The number of columns of a divider row around its title: the spaces and the bars after them.
+const DIVIDER_MARGIN: usize = 2;
+
+/// The outer width of the boxes of [`Renderer::classic`].
+const CLASSIC_WIDTH: usize = 80;
+
+/// The markers embedded into the borders of a viewport when there are lines above or below it,
+/// with their ASCII fallbacks. See [`Renderer::render_viewport`].
+const MORE_ABOVE: [&str; 2] = [" ▲ more ", " ^ more "];
+const MORE_BELOW: [&str; 2] = [" ▼ more ", " v more "];
+
+/// The sign placed between a repeated line and the number of its repeats, with its ASCII fallback.
+const REPEATS: [&str; 2] = ["×", "x"];
+
+/// The maximum number of lines rendered by the fast path that skips allocating the rows.
+const SMALL_INPUT_LINES: usize = 8;
+
+/// The number of lines starting from which the rows are measured and formatted in parallel.
+#[cfg(feature = "rayon")]
+const PARALLEL_THRESHOLD: usize = 16 * 1024;
+
+/// Writes the rows of a box into a sink, separated by the line ending of the config,
+/// and ends the output with it if the config asks for it.
+struct RowWriter<'w, 'c, W: ?Sized> {
+    out: &'w mut W,
+    line_ending: &'c str,
+    trailing_newline: bool,
+    written: usize,
+}
+
+impl<'w, 'c, W: fmt::Write + ?Sized> RowWriter<'w, 'c, W> {
+    fn new(out: &'w mut W, config: &TextBoxConfig<'c>) -> Self {
+        Self {
+            out,
+            line_ending: config.line_ending,
+            trailing_newline: config.trailing_newline,
+            written: 0,
+        }
+    }
+
+    /// Write the row on a separate line.
+    fn row(&mut self, row: &str) -> fmt::Result {
+        if self.written > 0 {
+            self.out.write_str(self.line_ending)?;
+        }
+        self.written += 1;
+        self.out.write_str(row)
+    }
+
+    /// End the output.
+    fn finish(self) -> fmt::Result {
+        match self.trailing_newline {
+            true => self.out.write_str(self.line_ending),
+            false => Ok(()),
+        }
+    }
+}
+
+/// A reusable message box renderer.
+///
+/// When constructed with a fixed width, the borders and the padding are computed once
+/// and reused by every call to [`render`](Renderer::render), making it cheap to
+/// render many boxes of the same shape.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// let renderer = Renderer::with_width(TextBoxConfig::default().with_caption("Status"), 16);
+///
+/// assert_eq!(renderer.render(&[String::from("OK")]), "\
+/// ╭──────────────╮
+/// │ OK           │
+/// <Status>───────╯");
+///
+/// assert_eq!(renderer.render(&[String::from("Shutting down")]), "\
+/// ╭──────────────╮
+/// │ Shutting dow │
+/// <Status>───────╯");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Renderer<'a> {
+    config: TextBoxConfig<'a>,
+    fixed: Option<Borders>,
+}
+
+impl<'a> Renderer<'a> {
+    /// Create a renderer that fits the width of every box to its content,
+    /// unless the config has a [fixed width](TextBoxConfig::with_width).
+    pub fn new(config: TextBoxConfig<'a>) -> Self {
+        if let Some(width) = config.width {
+            return Self::with_width(config, width);
+        }
+        let config = config.charset.apply(config);
+        Self {
+            config,
+            fixed: None,
+        }
+    }
+
+    /// Create a renderer producing boxes that are exactly `width` columns wide, borders included.
+    /// Lines that don't fit into the box are truncated, or wrapped with [`OverflowPolicy::Wrap`].
+    /// The given width takes precedence over the one of the config.
+    pub fn with_width(config: TextBoxConfig<'a>, width: usize) -> Self {
+        let config = config.charset.apply(config);
+        let inner_width = width.saturating_sub(BORDER_WIDTH);
+        let fixed = Borders::new(&config, config.measure(), inner_width, true);
+        Self {
+            config,
+            fixed: Some(fixed),
+        }
+    }
+
+    /// Create a renderer for the classic 80-column terminal: the boxes are exactly 80 columns
+    /// wide, borders included, and the lines that don't fit are wrapped instead of truncated.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let renderer = Renderer::classic(TextBoxConfig::default());
+    /// let msg = renderer.render(&["word ".repeat(20)]);
+    ///
+    /// assert!(msg.lines().all(|row| row.chars().count() == 80));
+    /// assert_eq!(msg.lines().count(), 4);
+    /// ```
+    pub fn classic(config: TextBoxConfig<'a>) -> Self {
+        Self::with_width(config.with_overflow(OverflowPolicy::Wrap), CLASSIC_WIDTH)
+    }
+
+    /// Create a renderer for the snapshot tests, producing the same output in every environment:
+    /// the boxes are exactly `width` columns wide, drawn with the ASCII characters, and never styled.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let highlight = |_: usize, _: &str| LineStyle::new().with_style(Style::new().bold());
+    /// let config = TextBoxConfig::default().with_caption("Status").with_line_style(&highlight);
+    /// let renderer = Renderer::snapshot(config, 14);
+    ///
+    /// assert_eq!(renderer.render(&[String::from("OK")]), "\
+    /// +------------+
+    /// | OK         |
+    /// <Status>-----+");
+    /// ```
+    pub fn snapshot(config: TextBoxConfig<'a>, width: usize) -> Self {
+        let ascii = TextBoxConfig::ascii();
+        let config = TextBoxConfig {
+            horizontal_bar: ascii.horizontal_bar,
+            vertical_bar: ascii.vertical_bar,
+            left_top_corner: ascii.left_top_corner,
+            left_bottom_corner: ascii.left_bottom_corner,
+            right_top_corner: ascii.right_top_corner,
+            right_bottom_corner: ascii.right_bottom_corner,
+            left_tee: ascii.left_tee,
+            right_tee: ascii.right_tee,
+            tail: ascii.tail,
+            column_divider: ascii.column_divider,
+            border: None,
+            strip_styles: true,
+            ..config
+        };
+        Self::with_width(config, width)
+    }
+
+    /// The config used by this renderer.
+    pub fn config(&self) -> &TextBoxConfig<'a> {
+        &self.config
+    }
+
+    /// The fixed width of the boxes produced by this renderer, if any.
+    pub fn width(&self) -> Option<usize> {
+        self.fixed
+            .as_ref()
+            .map(|borders| borders.inner_width + BORDER_WIDTH)
+    }
+
+    /// Measure the width of the box that would be rendered for the given lines, borders included.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let renderer = Renderer::new(TextBoxConfig::default());
+    /// let lines = [String::from("abc")];
+    ///
+    /// assert_eq!(renderer.measure(&lines), 7);
+    /// assert_eq!(renderer.render(&lines).lines().next().unwrap().chars().count(), 7);
+    /// ```
+    pub fn measure(&self, lines: &[String]) -> usize {
+        if let Some(width) = self.fixed_width() {
+            return width;
+        }
+
+        let lines = clean_lines(&self.config, lines);
+        let rows = self.rows(&lines, None);
+        let longest_line = max_item(&rows, Row::width);
+        let width = fit_caption(&self.config, self.config.measure(), longest_line, false).1;
+        self.width().unwrap_or(0).max(width + BORDER_WIDTH)
+    }
+
+    /// Measure the width of the box that would be rendered for the given rich text lines,
+    /// borders included. See [`render_styled`](Self::render_styled).
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let renderer = Renderer::new(TextBoxConfig::default());
+    /// let line = StyledLine::new().with_span("abc", Style::new().bold());
+    ///
+    /// assert_eq!(renderer.measure_styled(&[line]), 7);
+    /// ```
+    pub fn measure_styled(&self, lines: &[StyledLine<'_>]) -> usize {
+        if let Some(width) = self.fixed_width() {
+            return width;
+        }
+
+        let rows = self.layout(lines.len(), |rows| self.extend_styled(rows, lines));
+        let longest_line = max_item(&rows, Row::width);
+        let width = fit_caption(&self.config, self.config.measure(), longest_line, false).1;
+        self.width().unwrap_or(0).max(width + BORDER_WIDTH)
+    }
+
+    /// The width of every box, unless the fixed width is [expanded](OverflowPolicy::Expand).
+    fn fixed_width(&self) -> Option<usize> {
+        self.width()
+            .filter(|_| self.config.overflow != OverflowPolicy::Expand)
+    }
+
+    /// Render the given lines as a message box.
+    pub fn render(&self, lines: &[String]) -> String {
+        let lines = clean_lines(&self.config, lines);
+        if lines.len() <= SMALL_INPUT_LINES && self.is_plain() {
+            self.render_small(&lines)
+        } else {
+            self.render_rows(&lines)
+        }
+    }
+
+    /// Write the given lines as a message box into the sink, like the formatter of a `Display`
+    /// implementation. The rows are laid out and measured first, then every row is written
+    /// as soon as it's finished, without collecting the box into a string.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let mut out = String::from("status:\n");
+    /// Renderer::new(TextBoxConfig::ascii()).render_into(&mut out, &[String::from("ok")])?;
+    /// assert_eq!(out, "status:\n+----+\n| ok |\n+----+");
+    /// # std::fmt::Result::Ok(())
+    /// ```
+    pub fn render_into<W: fmt::Write + ?Sized>(
+        &self,
+        out: &mut W,
+        lines: &[String],
+    ) -> fmt::Result {
+        let lines = clean_lines(&self.config, lines);
+        if lines.len() <= SMALL_INPUT_LINES && self.is_plain() {
+            return self.write_small(out, &lines);
+        }
+
+        let rows = self.rows(&lines, self.content_limit());
+        let borders = self.borders(&rows);
+        let top = iter::once_with(|| self.finish_row(0, RowKind::Border, borders.top.clone()));
+        let content = rows.iter().enumerate().map(|(index, row)| {
+            self.finish_row(index + 1, row.kind(), borders.render_row(row, &self.config))
+        });
+        self.write_bordered(out, &borders, rows.len(), top.chain(content))
+    }
+
+    /// Render the given rich text lines as a message box. Only the text of the spans
+    /// counts towards the width of the box, and the padding is never styled.
+    /// The lines are always laid out as rows, regardless of the orientation.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let line = StyledLine::new()
+    ///     .with_span("status: ", Style::new())
+    ///     .with_span("ok", Style::new().fg(Color::Green));
+    /// let renderer = Renderer::new(TextBoxConfig::default());
+    ///
+    /// assert_eq!(renderer.render_styled(&[line, StyledLine::from("uptime: 3d")]), "\
+    /// ╭────────────╮
+    /// │ status: \x1b[32mok\x1b[0m │
+    /// │ uptime: 3d │
+    /// ╰────────────╯");
+    /// ```
+    pub fn render_styled(&self, lines: &[StyledLine<'_>]) -> String {
+        let rows = self.layout(lines.len(), |rows| self.extend_styled(rows, lines));
+        self.render_layout(&rows)
+    }
+
+    /// Render the given lines as a message box like [`render`](Self::render), unless the
+    /// caption doesn't fit into it: the width of the box is fixed, or limited by the
+    /// [`max_width`](TextBoxConfig::max_width) or the [`truncate_caption`](TextBoxConfig::truncate_caption)
+    /// option of the config, and the caption is wider than the content.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let config = TextBoxConfig::default().with_caption("Configuration");
+    /// let lines = [String::from("ok")];
+    ///
+    /// let error = Renderer::with_width(config, 12).try_render(&lines).unwrap_err();
+    /// assert_eq!(error, CaptionOverflow { caption_width: 13, available_width: 8 });
+    /// assert_eq!(
+    ///     error.to_string(),
+    ///     "the caption is 13 columns wide, but only 8 columns are available"
+    /// );
+    ///
+    /// let expanded = config.with_overflow(OverflowPolicy::Expand);
+    /// assert!(Renderer::with_width(expanded, 12).try_render(&lines).is_ok());
+    /// ```
+    pub fn try_render(&self, lines: &[String]) -> Result<String, CaptionOverflow> {
+        if let Some(caption) = self.config.last_line_caption {
+            let caption_width = self.config.measure().width(caption);
+            let available_width = self.measure(lines).saturating_sub(BORDER_WIDTH);
+            if caption_width > available_width {
+                return Err(CaptionOverflow {
+                    caption_width,
+                    available_width,
+                });
+            }
+        }
+        Ok(self.render(lines))
+    }
+
+    /// Render the given lines, each with its own alignment, indent and style, as a message box.
+    /// See [`Line`].
+    pub fn render_lines(&self, lines: &[Line<'_>]) -> String {
+        let rows = self.layout(lines.len(), |rows| {
+            let lines = map_items(lines, |_, line| self.rich_rows(line));
+            self.extend_spaced(rows, lines)
+        });
+        self.render_layout(&rows)
+    }
+
+    /// Render the given key-value entries as a message box, see [`generate_key_values`](crate::generate_key_values).
+    /// In a box with a fixed width, the values that don't fit are wrapped, and their
+    /// continuation lines start at the value column.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let entries = [
+    ///     KeyValue::new("PATH:", "/usr/local/bin:/usr/bin"),
+    ///     KeyValue::new("SHELL:", "/bin/zsh"),
+    /// ];
+    /// let renderer = Renderer::with_width(TextBoxConfig::default(), 26);
+    ///
+    /// assert_eq!(renderer.render_key_values(&entries), "\
+    /// ╭────────────────────────╮
+    /// │ PATH:  /usr/local/bin: │
+    /// │        /usr/bin        │
+    /// │ SHELL: /bin/zsh        │
+    /// ╰────────────────────────╯");
+    /// ```
+    pub fn render_key_values(&self, entries: &[KeyValue<'_>]) -> String {
+        let max_width = self.content_limit();
+        let lines = kv::layout(entries, self.config.measure(), max_width);
+        self.render(&lines)
+    }
+
+    /// Render the given groups of lines in one box, separated by horizontal rules.
+    /// See [`generate_separated`](crate::generate_separated).
+    pub fn render_separated(&self, groups: &[&[String]]) -> String {
+        let groups = map_items(groups, |_, group| split_input(&self.config, group));
+        let lines = groups.iter().map(|group| group.len()).sum::<usize>();
+        let rows = self.layout(lines + groups.len(), |rows| {
+            let mut index = 0;
+            for group in groups.iter().filter(|group| !group.is_empty()) {
+                if index > 0 {
+                    rows.push(Row::Rule);
+                }
+                self.extend_lines(rows, group, index);
+                index += group.len();
+            }
+        });
+        self.render_layout(&rows)
+    }
+
+    /// Render the given groups of lines in one box, separated by the horizontal rules with
+    /// the titles of the groups embedded, if any. A titled group starts with its divider
+    /// even if it's the first one.
+    pub(crate) fn render_divided(&self, groups: &[(Option<&str>, Vec<String>)]) -> String {
+        let groups = map_items(groups, |_, (title, group)| {
+            (*title, split_input(&self.config, group))
+        });
+        let lines = groups.iter().map(|(_, group)| group.len()).sum::<usize>();
+        let rows = self.layout(lines + groups.len(), |rows| {
+            let mut index = 0;
+            for (number, (title, group)) in groups.iter().enumerate() {
+                match title {
+                    Some(title) => rows.push(self.divider_row(title)),
+                    None if number > 0 => rows.push(Row::Rule),
+                    None => {}
+                }
+                self.extend_lines(rows, group, index);
+                index += group.len();
+            }
+        });
+        self.render_layout(&rows)
+    }
+
+    /// Create a divider row with the cleaned title embedded, truncating it to the fixed width
+    /// if there's one.
+    fn divider_row<'l>(&self, title: &'l str) -> Row<'l> {
+        let measure = self.config.measure();
+        let title = clean_input(&self.config, title);
+        let title = match self.content_limit() {
+            Some(limit) => measure::truncate(measure, title, limit.saturating_sub(DIVIDER_MARGIN)),
+            None => title,
+        };
+        Row::Divider {
+            width: measure.width(&title),
+            title,
+        }
+    }
+
+    /// Render the given sections in one box, separated by horizontal rules. Every section starts
+    /// with a heading row, and the lines of the collapsed ones are replaced with their number.
+    /// See [`generate_sections`](crate::generate_sections).
+    pub fn render_sections(&self, sections: &[Section<'_>]) -> String {
+        let config = &self.config;
+        let headings = map_items(sections, |_, section| {
+            split_input(config, &[section.heading(config)]).into_owned()
+        });
+        let bodies = map_items(sections, |_, section| split_input(config, &section.lines));
+        let lines = sections
+            .iter()
+            .zip(&bodies)
+            .filter(|(section, _)| !section.is_hidden(config))
+            .map(|(_, lines)| lines.len())
+            .sum::<usize>();
+
+        let rows = self.layout(lines + sections.len() * 2, |rows| {
+            let mut index = 0;
+            for ((section, heading), lines) in sections.iter().zip(&headings).zip(&bodies) {
+                if index > 0 {
+                    rows.push(Row::Rule);
+                }
+                self.extend_lines(rows, heading, index);
+                index += heading.len();
+                if !section.is_hidden(config) {
+                    self.extend_lines(rows, lines, index);
+                    index += lines.len();
+                }
+            }
+        });
+        self.render_layout(&rows)
+    }
+
+    /// Split the lines into pages of at most `page_lines` lines and render every page as a separate box.
+    /// The `{page}` and `{pages}` placeholders of the caption are replaced with the number
+    /// of the page, starting from 1, and the number of the pages.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let renderer = Renderer::new(TextBoxConfig::default().with_caption("log {page}/{pages}"));
+    /// let lines = ["started", "listening", "stopped"].map(String::from);
+    /// let pages = renderer.render_pages(&lines, 2);
+    ///
+    /// assert_eq!(pages[1], "\
+    /// ╭─────────╮
+    /// │ stopped │
+    /// <log 2/2>─╯");
+    /// ```
+    pub fn render_pages(&self, lines: &[String], page_lines: usize) -> Vec<String> {
+        let mut pages = lines.chunks(page_lines.max(1)).collect::<Vec<_>>();
+        if pages.is_empty() {
+            pages.push(&[]);
+        }
+
+        let count = pages.len();
+        pages
+            .iter()
+            .enumerate()
+            .map(|(index, page)| {
+                let value = |key: &str| match key {
+                    "page" => Some((index + 1).to_string()),
+                    "pages" => Some(count.to_string()),
+                    _ => None,
+                };
+                let caption = self.config.last_line_caption.map(|c| fill(c, value));
+                let config = TextBoxConfig {
+                    last_line_caption: caption.as_deref(),
+                    ..self.config
+                };
+                match self.width() {
+                    Some(width) => Renderer::with_width(config, width).render(page),
+                    None => Renderer::new(config).render(page),
+                }
+            })
+            .collect()
+    }
+
+    /// Render the window of `height` lines starting at `offset` in a box of a fixed size,
+    /// for scrolling through the lines that don't fit into the screen. The window is padded
+    /// with the blank rows at the end of the lines, and the `▲ more` and `▼ more` markers are
+    /// embedded into the top and the bottom borders if there are lines above or below it,
+    /// spelled with `^` and `v` in the boxes drawn with the ASCII characters. Without a fixed
+    /// width, the box is as wide as the one rendered for all of the lines. In the
+    /// [column mode](TextBoxConfig::columns), the window scrolls through the rows of the columns.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let lines = (1..=5).map(|n| format!("line {}", n)).collect::<Vec<_>>();
+    /// let renderer = Renderer::with_width(TextBoxConfig::default(), 16);
+    ///
+    /// assert_eq!(renderer.render_viewport(&lines, 2, 2), "\
+    /// ╭───── ▲ more ─╮
+    /// │ line 3       │
+    /// │ line 4       │
+    /// ╰───── ▼ more ─╯");
+    /// assert_eq!(renderer.render_viewport(&lines, 4, 2), "\
+    /// ╭───── ▲ more ─╮
+    /// │ line 5       │
+    /// │              │
+    /// ╰──────────────╯");
+    /// ```
+    pub fn render_viewport(&self, lines: &[String], offset: usize, height: usize) -> String {
+        let lines = split_input(&self.config, lines);
+        let columns = match self.config.orientation {
+            Orientation::Rows => None,
+            Orientation::Columns => Some(transpose(&lines, &self.config, self.content_limit())),
+        };
+        let count = columns.as_ref().map_or(lines.len(), Vec::len);
+        let start = offset.min(count);
+        let end = count.min(start.saturating_add(height));
+        let alignment = self.default_alignment();
+        let rows = self.layout(height, |rows| {
+            let first = rows.len();
+            match &columns {
+                Some(columns) => rows.extend(columns[start..end].iter().cloned()),
+                None => self.extend_lines(rows, &lines[start..end], start),
+            }
+            let missing_rows = height.saturating_sub(rows.len() - first);
+            rows.extend((0..missing_rows).map(|_| Row::blank(alignment)));
+        });
+
+        let borders = match &self.fixed {
+            Some(_) => self.borders(&rows).into_owned(),
+            None => {
+                let longest_line = match &columns {
+                    Some(columns) => max_item(columns, Row::width),
+                    None => max_item(&self.rows(&lines, None), Row::width),
+                };
+                let inner_width = longest_line.max(max_item(&rows, Row::width));
+                Borders::new(&self.config, self.config.measure(), inner_width, false)
+            }
+        };
+        let marker = |shown: bool, marker| Some(glyph(&self.config, marker)).filter(|_| shown);
+        let borders = borders.with_markers(
+            &self.config,
+            self.config.measure(),
+            marker(start > 0, MORE_ABOVE),
+            marker(end < count, MORE_BELOW),
+        );
+        self.render_bordered(&rows, &borders)
+    }
+
+    /// Render two sets of lines side by side as the left and the right columns of the box,
+    /// separated by the [`column_divider`](TextBoxConfig::column_divider).
+    /// See [`generate_split`](crate::generate_split).
+    pub fn render_split(&self, left: &[String], right: &[String]) -> String {
+        let (left, right) = (
+            split_input(&self.config, left),
+            split_input(&self.config, right),
+        );
+        let rows = self.layout(left.len().max(right.len()), |rows| {
+            rows.extend(self.split_rows(&left, &right))
+        });
+        self.render_layout(&rows)
+    }
+
+    /// Lay out the rows of the left and the right columns. The columns are padded to the width
+    /// of their longest lines, and the right one is truncated first if the box has a fixed width.
+    /// Every input line keeps its row even if the columns are truncated down to nothing.
+    fn split_rows<'l>(&self, left: &[String], right: &[String]) -> Vec<Row<'l>> {
+        let config = &self.config;
+        let measure = self.config.measure();
+        let divider = config.column_divider;
+        let divider_width = glyph_width(config, divider) + 2;
+
+        let column_width = |lines: &[String]| {
+            lines
+                .iter()
+                .map(|line| measure.width(trim_input(config, line)))
+                .max()
+                .unwrap_or(0)
+        };
+        let mut left_width = column_width(left);
+        let mut right_width = column_width(right);
+        if let Some(limit) = self.content_limit() {
+            let available = limit.saturating_sub(divider_width);
+            left_width = left_width.min(available);
+            right_width = right_width.min(available - left_width);
+        }
+        let width = left_width + divider_width + right_width;
+        // The divider alone doesn't fit, so the columns are empty and the rows are truncated.
+        let limit = self.content_limit().filter(|&limit| width > limit);
+
+        let cell = |lines: &[String], index: usize, width: usize, out: &mut String| {
+            let line = lines.get(index).map_or("", |line| trim_input(config, line));
+            let line = measure.truncate(line, width);
+            let padding = width - measure.width(line);
+            if config.align_numbers && is_numeric(line) {
+                push_spaces(out, padding);
+                out.push_str(line);
+            } else {
+                out.push_str(line);
+                push_spaces(out, padding);
+            }
+        };
+        (0..left.len().max(right.len()))
+            .map(|index| {
+                let mut text = String::with_capacity(width + divider.len());
+                cell(left, index, left_width, &mut text);
+                text.push(' ');
+                text.push_str(divider);
+                text.push(' ');
+                cell(right, index, right_width, &mut text);
+                let (text, width) = match limit {
+                    Some(limit) => {
+                        let text = measure::truncate(measure, text.into(), limit);
+                        let width = measure.width(&text);
+                        (text, width)
+                    }
+                    None => (text.into(), width),
+                };
+                Row::Content {
+                    text,
+                    width,
+                    alignment: self.default_alignment(),
+                    border_glyph: None,
+                }
+            })
+            .collect()
+    }
+
+    /// Render the box by laying out all of its rows first.
+    fn render_rows(&self, lines: &[String]) -> String {
+        let max_width = self.content_limit();
+        self.render_layout(&self.rows(lines, max_width))
+    }
+
+    /// Lay out the given lines without rendering them, see [`RenderPlan`].
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let renderer = Renderer::new(TextBoxConfig::default());
+    /// let mut plan = renderer.plan(&[String::from("Build"), String::from("Test")]);
+    /// plan.rows.insert(1, Row::Rule);
+    /// plan.rows.push(plan.line("Deploy"));
+    ///
+    /// assert_eq!(plan.width(), 10);
+    /// assert_eq!(plan.render(), "\
+    /// ╭────────╮
+    /// │ Build  │
+    /// ├────────┤
+    /// │ Test   │
+    /// │ Deploy │
+    /// ╰────────╯");
+    /// ```
+    pub fn plan(&self, lines: &[String]) -> RenderPlan<'_, 'a> {
+        let lines = clean_lines(&self.config, lines);
+        let max_width = self.content_limit();
+        let rows = self.rows(&lines, max_width);
+        RenderPlan::new(self, rows.into_iter().map(Row::into_owned).collect())
+    }
+
+    /// Iterate over the structural parts of the box that would be rendered for the given lines,
+    /// see [`BoxParts`].
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let renderer = Renderer::new(TextBoxConfig::default().with_caption("Log"));
+    /// let lines = [String::from("Started")];
+    /// let html = renderer
+    ///     .parts(&lines)
+    ///     .map(|part| match part {
+    ///         BoxPart::TopBorder => String::from("<div class=\"box\">"),
+    ///         BoxPart::Content(text) => format!("<p>{}</p>", text),
+    ///         BoxPart::Separator => String::from("<hr>"),
+    ///         BoxPart::Divider(title) => format!("<h2>{}</h2>", title),
+    ///         BoxPart::BottomBorder { caption } => {
+    ///             format!("<footer>{}</footer></div>", caption.unwrap_or_default())
+    ///         }
+    ///     })
+    ///     .collect::<String>();
+    ///
+    /// assert_eq!(html, r#"<div class="box"><p>Started</p><footer>Log</footer></div>"#);
+    /// ```
+    pub fn parts<'l>(&self, lines: &'l [String]) -> BoxParts<'l> {
+        let max_width = self.content_limit();
+        let rows = match clean_lines(&self.config, lines) {
+            Cow::Borrowed(lines) => self.rows(lines, max_width),
+            Cow::Owned(lines) => self
+                .rows(&lines, max_width)
+                .into_iter()
+                .map(Row::into_owned)
+                .collect(),
+        };
+        let borders = self.borders(&rows);
+        BoxParts::new(borders.inner_width, borders.caption.clone(), rows)
+    }
+
+    /// Render the given rows between the borders of the box.
+    pub(crate) fn render_layout(&self, rows: &[Row<'_>]) -> String {
+        self.render_bordered(rows, &self.borders(rows))
+    }
+
+    /// Render the given rows between the given borders.
+    fn render_bordered(&self, rows: &[Row<'_>], borders: &Borders) -> String {
+        let config = &self.config;
+        let top = self.finish_row(0, RowKind::Border, borders.top.clone());
+        let content = map_items(rows, |index, row| {
+            self.finish_row(index + 1, row.kind(), borders.render_row(row, config))
+        });
+
+        let len = top.len() * 3 + content.iter().map(String::len).sum::<usize>();
+        let mut result = String::with_capacity(len + config.line_ending.len() * (rows.len() + 3));
+        self.write_bordered(
+            &mut result,
+            borders,
+            rows.len(),
+            iter::once(top).chain(content),
+        )
+        .expect("writing into a string doesn't fail");
+        result
+    }
+
+    /// Write the finished top border and `rows` content rows, followed by the bottom border,
+    /// into the sink.
+    fn write_bordered<W: fmt::Write + ?Sized>(
+        &self,
+        out: &mut W,
+        borders: &Borders,
+        rows: usize,
+        finished: impl Iterator<Item = String>,
+    ) -> fmt::Result {
+        let config = &self.config;
+
+        let mut writer = RowWriter::new(out, config);
+        for row in finished {
+            writer.row(&row)?;
+        }
+        writer.row(&self.finish_row(rows + 1, self.bottom_kind(), borders.bottom.clone()))?;
+
+        if let Some(column) = config.tail_column {
+            let mut tail = String::with_capacity(column + config.tail.len());
+            push_spaces(&mut tail, column);
+            tail.push_str(config.tail);
+            writer.row(&self.finish_row(rows + 2, RowKind::Tail, tail))?;
+        }
+        writer.finish()
+    }
+
+    /// Describe the layout of the box that would be rendered for the given lines as JSON:
+    /// the width of the box, the caption and its position, and every output row with its kind.
+    /// The content rows also have the alignment and the text before it's padded.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let renderer = Renderer::new(TextBoxConfig::default().with_caption("C"));
+    ///
+    /// assert_eq!(
+    ///     renderer.render_json(&[String::from("ok")]),
+    ///     concat!(
+    ///         r#"{"width":6,"height":3,"caption":{"text":"C","position":"left"},"rows":["#,
+    ///         r#"{"kind":"border","text":"╭────╮"},"#,
+    ///         r#"{"kind":"content","text":"│ ok │","content":"ok","alignment":"left"},"#,
+    ///         r#"{"kind":"caption","text":"<C>──╯"}]}"#,
+    ///     )
+    /// );
+    /// ```
+    pub fn render_json(&self, lines: &[String]) -> String {
+        let config = &self.config;
+        let lines = clean_lines(config, lines);
+        let max_width = self.content_limit();
+        let rows = self.rows(&lines, max_width);
+        let borders = self.borders(&rows);
+        let height = rows.len() + 2 + config.tail_column.is_some() as usize;
+
+        let mut json = String::new();
+        json.push_str(&format!(
+            r#"{{"width":{},"height":{},"caption":"#,
+            borders.inner_width + BORDER_WIDTH,
+            height
+        ));
+        match &borders.caption {
+            Some(caption) => {
+                json.push_str(r#"{"text":"#);
+                json::push_string(&mut json, caption);
+                let position = json::alignment(caption_alignment(config));
+                json.push_str(&format!(r#","position":"{}"}}"#, position));
+            }
+            None => json.push_str("null"),
+        }
+
+        json.push_str(r#","rows":["#);
+        let push_row = |json: &mut String, index: usize, kind: RowKind, text: String| {
+            if index > 0 {
+                json.push(',');
+            }
+            json.push_str(&format!(r#"{{"kind":"{}","text":"#, json::row_kind(kind)));
+            json::push_string(json, &self.finish_row(index, kind, text));
+        };
+        push_row(&mut json, 0, RowKind::Border, borders.top.clone());
+        json.push('}');
+        for (index, row) in rows.iter().enumerate() {
+            push_row(
+                &mut json,
+                index + 1,
+                row.kind(),
+                borders.render_row(row, config),
+            );
+            if let Row::Content {
+                text, alignment, ..
+            } = row
+            {
+                json.push_str(r#","content":"#);
+                json::push_string(&mut json, text);
+                json.push_str(&format!(
+                    r#","alignment":"{}""#,
+                    json::alignment(*alignment)
+                ));
+            }
+            json.push('}');
+        }
+        push_row(
+            &mut json,
+            rows.len() + 1,
+            self.bottom_kind(),
+            borders.bottom.clone(),
+        );
+        json.push('}');
+        if let Some(column) = config.tail_column {
+            let tail = format!("{:column$}{}", "", config.tail, column = column);
+            push_row(&mut json, rows.len() + 2, RowKind::Tail, tail);
+            json.push('}');
+        }
+        json.push_str("]}");
+        json
+    }
+
+    /// The borders of the box with the given rows, precomputed if the box has a fixed width.
+    /// With [`OverflowPolicy::Expand`], the fixed borders are widened to fit the rows and the caption.
+    pub(crate) fn borders(&self, rows: &[Row<'_>]) -> Cow<'_, Borders> {
+        match &self.fixed {
+            Some(borders) if self.config.overflow != OverflowPolicy::Expand => {
+                Cow::Borrowed(borders)
+            }
+            Some(borders) => {
+                let longest_line = max_item(rows, Row::width).max(borders.inner_width);
+                let expanded =
+                    Borders::new(&self.config, self.config.measure(), longest_line, false);
+                if expanded.inner_width > borders.inner_width {
+                    Cow::Owned(expanded)
+                } else {
+                    Cow::Borrowed(borders)
+                }
+            }
+            None => {
+                let longest_line = max_item(rows, Row::width);
+                Cow::Owned(Borders::new(
+                    &self.config,
+                    self.config.measure(),
+                    longest_line,
+                    false,
+                ))
+            }
+        }
+    }
+
+    /// Whether the config only has the features supported by [`render_small`](Self::render_small).
+    fn is_plain(&self) -> bool {
+        let config = &self.config;
+        config.timestamp.is_none()
+            && config.footer.is_none()
+            && config.side_caption.is_none()
+            && config.orientation == Orientation::Rows
+            && config.line_style.is_none()
+            && config.post_render.is_none()
+            && !config.markup
+            && config.line_spacing == 0
+            && config.vertical_padding == 0
+            && config.indent == 0
+            && config.row_prefix.is_empty()
+            && config.row_suffix.is_empty()
+            && config.border.is_none()
+            && config.measurer.is_none()
+            && config.padding_fill.is_none()
+            && config.highlighter.is_none()
+            && config.max_width.is_none()
+            && config.ellipsis.is_none()
+            && config.overflow == OverflowPolicy::Truncate
+    }
+
+    /// Render a box with a handful of lines, see [`write_small`](Self::write_small).
+    fn render_small(&self, lines: &[String]) -> String {
+        let mut result = String::new();
+        self.write_small(&mut result, lines)
+            .expect("writing into a string doesn't fail");
+        result
+    }
+
+    /// Write a box with a handful of lines directly into the sink, reusing a single buffer
+    /// for all of the rows.
+    fn write_small<W: fmt::Write + ?Sized>(&self, out: &mut W, lines: &[String]) -> fmt::Result {
+        let config = &self.config;
+
+        let mut texts = [""; SMALL_INPUT_LINES];
+        for (text, line) in texts.iter_mut().zip(lines) {
+            let line = trim_input(config, line);
+            *text = match &self.fixed {
+                Some(borders) => self.config.measure().truncate(line, borders.inner_width),
+                None => line,
+            };
+        }
+        let texts = &texts[..lines.len()];
+
+        let longest_line = texts
+            .iter()
+            .map(|text| self.config.measure().width(text))
+            .max()
+            .unwrap_or(0);
+        let (caption, inner_width) = match &self.fixed {
+            Some(borders) => (None, borders.inner_width),
+            None => fit_caption(config, self.config.measure(), longest_line, false),
+        };
+
+        let mut writer = RowWriter::new(out, config);
+        let mut row = String::with_capacity(config.horizontal_bar.len() * (inner_width + 2) + 8);
+        match &self.fixed {
+            Some(borders) => writer.row(&borders.top)?,
+            None => {
+                push_top(&mut row, config, inner_width, None);
+                writer.row(&row)?;
+            }
+        }
+        for text in texts {
+            row.clear();
+            push_content(
+                &mut row,
+                (text, self.config.measure().width(text)),
+                inner_width,
+                self.default_alignment(),
+                (config.vertical_bar, right_bar(config, config.vertical_bar)),
+                None,
+            );
+            writer.row(&row)?;
+        }
+        row.clear();
+        match &self.fixed {
+            Some(borders) => writer.row(&borders.bottom)?,
+            None => {
+                push_bottom(
+                    &mut row,
+                    config,
+                    self.config.measure(),
+                    inner_width,
+                    caption.as_deref(),
+                    None,
+                );
+                writer.row(&row)?;
+            }
+        }
+
+        if let Some(column) = config.tail_column {
+            row.clear();
+            push_spaces(&mut row, column);
+            row.push_str(config.tail);
+            writer.row(&row)?;
+        }
+        writer.finish()
+    }
+
+    /// Render the lines produced by the given source as a message box.
+    /// The lines are collected first, since the width of the box depends on all of them.
+    /// See [`stream_source`](Self::stream_source) for the boxes with a fixed width.
+    pub fn render_source(&self, mut source: impl LineSource) -> io::Result<String> {
+        let mut lines = Vec::with_capacity(source.size_hint().unwrap_or(0));
+        while let Some(line) = source.next_line()? {
+            lines.push(line);
+        }
+        Ok(self.render(&lines))
+    }
+
+    /// Stream a box with the fixed width displaying the lines produced by the given source
+    /// into the given writer, without collecting the lines. Fails if the renderer doesn't have
+    /// a fixed width, see [`stream`](Self::stream).
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// use std::io::BufRead;
+    ///
+    /// let log = std::io::Cursor::new("Started\nFinished\n");
+    /// let renderer = Renderer::with_width(TextBoxConfig::default(), 12);
+    /// let output = renderer.stream_source(log.lines(), Vec::new())?;
+    ///
+    /// assert_eq!(String::from_utf8(output).unwrap(), "\
+    /// ╭──────────╮
+    /// │ Started  │
+    /// │ Finished │
+    /// ╰──────────╯");
+    /// # std::io::Result::Ok(())
+    /// ```
+    pub fn stream_source<W: io::Write>(
+        &self,
+        mut source: impl LineSource,
+        writer: W,
+    ) -> io::Result<W> {
+        let mut stream = self.stream(writer)?;
+        while let Some(line) = source.next_line()? {
+            stream.write_line(&line)?;
+        }
+        stream.finish()
+    }
+
+    /// Start streaming a box with the fixed width into the given writer.
+    /// The top border is written immediately. Fails if the renderer doesn't have a fixed width.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let renderer = Renderer::with_width(TextBoxConfig::default(), 12);
+    /// let mut stream = renderer.stream(Vec::new())?;
+    /// stream.write_line("Started")?;
+    /// stream.write_line("Finished")?;
+    /// let output = stream.finish()?;
+    ///
+    /// assert_eq!(String::from_utf8(output).unwrap(), "\
+    /// ╭──────────╮
+    /// │ Started  │
+    /// │ Finished │
+    /// ╰──────────╯");
+    /// # std::io::Result::Ok(())
+    /// ```
+    pub fn stream<W: io::Write>(&self, writer: W) -> io::Result<BoxStream<'_, 'a, W>> {
+        BoxStream::new(Cow::Borrowed(self), writer)
+    }
+
+    /// The precomputed borders if the renderer has a fixed width.
+    pub(crate) fn fixed_borders(&self) -> Option<&Borders> {
+        self.fixed.as_ref()
+    }
+
+    /// Pass the finished row through the post-render hook, if there's one,
+    /// then indent it and attach the row prefix and suffix.
+    pub(crate) fn finish_row(&self, index: usize, kind: RowKind, row: String) -> String {
+        let config = &self.config;
+        let row = match config.post_render {
+            Some(hook) => hook(index, kind, row),
+            None => row,
+        };
+        if config.indent == 0 && config.row_prefix.is_empty() && config.row_suffix.is_empty() {
+            return row;
+        }
+
+        let len = config.indent + config.row_prefix.len() + row.len() + config.row_suffix.len();
+        let mut decorated = String::with_capacity(len);
+        push_spaces(&mut decorated, config.indent);
+        decorated.push_str(config.row_prefix);
+        decorated.push_str(&row);
+        decorated.push_str(config.row_suffix);
+        decorated
+    }
+
+    /// The kind of the bottom border row.
+    pub(crate) fn bottom_kind(&self) -> RowKind {
+        if self.config.last_line_caption.is_some() {
+            RowKind::Caption
+        } else {
+            RowKind::Border
+        }
+    }
+
+    /// The alignment of the content rows that don't override it.
+    pub(crate) fn default_alignment(&self) -> Alignment {
+        if self.config.mirrored {
+            self.config.alignment.opposite()
+        } else {
+            self.config.alignment
+        }
+    }
+
+    /// Create a content row, truncating it to the fixed width if there's one.
+    pub(crate) fn content_row<'l>(&self, text: Cow<'l, str>, alignment: Alignment) -> Row<'l> {
+        let text = match self.content_limit() {
+            Some(limit) => {
+                measure::truncate_with(self.config.measure(), text, limit, self.config.ellipsis)
+            }
+            None => text,
+        };
+        Row::Content {
+            width: self.config.measure().width(&text),
+            text,
+            alignment,
+            border_glyph: None,
+        }
+    }
+
+    /// The content width the lines are wrapped at, if the config asks for the wrapping.
+    /// The lines are always wrapped at the [`max_width`](TextBoxConfig::max_width).
+    pub(crate) fn wrap_width(&self) -> Option<usize> {
+        match self.config.overflow {
+            OverflowPolicy::Wrap => self.content_limit(),
+            OverflowPolicy::Truncate => self.fixed.is_none().then(|| self.content_limit())?,
+            OverflowPolicy::Expand => None,
+        }
+    }
+
+    /// The widest content the rows can have: the content width of the box if it's fixed,
+    /// or the one of the [`max_width`](TextBoxConfig::max_width) of the config.
+    /// The rows aren't limited with [`OverflowPolicy::Expand`].
+    pub(crate) fn content_limit(&self) -> Option<usize> {
+        if self.config.overflow == OverflowPolicy::Expand {
+            return None;
+        }
+        match &self.fixed {
+            Some(borders) => Some(borders.inner_width),
+            None => max_inner_width(&self.config),
+        }
+    }
+
+    /// The width of the prefix added to the line with the given index by the line style hook.
+    fn prefix_width(&self, index: usize, line: &str) -> usize {
+        self.config.line_style.map_or(0, |hook| {
+            self.config.measure().width(&hook(index, line).prefix)
+        })
+    }
+
+    /// Create the rows for the input line with the given index, wrapping it if the config
+    /// asks for it. The styled lines are wrapped by their visible text.
+    pub(crate) fn line_rows<'l>(&self, index: usize, line: &'l str) -> Vec<Row<'l>> {
+        let config = &self.config;
+        let width = match self.wrap_width() {
+            Some(width) => width,
+            None => return vec![self.line_row(index, line)],
+        };
+
+        let line = trim_input(config, line);
+        if config.markup {
+            return self.styled_rows(index, &StyledLine::from_markup(line));
+        }
+        if let Some(line) = self.highlighted(line) {
+            return self.styled_rows(index, &line);
+        }
+        if ansi::find_escape(line).is_some() && config.measure().width(RESET) == 0 {
+            return self.styled_rows(index, &StyledLine::from_ansi(line));
+        }
+
+        let width = width.saturating_sub(self.prefix_width(index, line));
+        wrap(line, width, config.measure())
+            .into_iter()
+            .map(|range| self.line_row(index, &line[range]))
+            .collect()
+    }
+
+    /// Create the rows for the rich text line with the given index, see [`line_rows`](Self::line_rows).
+    pub(crate) fn styled_rows<'l>(&self, index: usize, line: &StyledLine<'_>) -> Vec<Row<'l>> {
+        let width = match self.wrap_width() {
+            Some(width) => width,
+            None => return vec![self.styled_row(index, line)],
+        };
+
+        let text = line.text();
+        let width = width.saturating_sub(self.prefix_width(index, &text));
+        wrap(&text, width, self.config.measure())
+            .into_iter()
+            .map(|range| self.styled_row(index, &line.slice(range)))
+            .collect()
+    }
+
+    /// Create the row for the input line with the given index, applying the line style hook.
+    pub(crate) fn line_row<'l>(&self, index: usize, line: &'l str) -> Row<'l> {
+        let line = trim_input(&self.config, line);
+        if self.config.markup {
+            return self.styled_row(index, &StyledLine::from_markup(line));
+        }
+        if let Some(line) = self.highlighted(line) {
+            return self.styled_row(index, &line);
+        }
+
+        let hook = match self.config.line_style {
+            Some(hook) => hook,
+            None => return self.content_row(line.into(), self.default_alignment()),
+        };
+
+        let style = hook(index, line);
+        let alignment = style.alignment.unwrap_or_else(|| self.default_alignment());
+        let text = if style.prefix.is_empty() {
+            Cow::Borrowed(line)
+        } else {
+            Cow::Owned(style.prefix.into_owned() + line)
+        };
+
+        let mut row = self.content_row(text, alignment);
+        if let Row::Content { text, .. } = &mut row {
+            *text = self.paint(style.style, std::mem::take(text));
+        }
+        row
+    }
+
+    /// The line split into the spans of its highlights, if the highlighter of the config finds any.
+    fn highlighted<'l>(&self, line: &'l str) -> Option<StyledLine<'l>> {
+        let highlights = self.config.highlighter?.highlights(line);
+        if highlights.is_empty() {
+            return None;
+        }
+        Some(highlight::apply(line, highlights, self.config.strip_styles))
+    }
+
+    /// Apply the style to the text unless the config strips the styles.
+    fn paint<'t>(&self, style: Style, text: impl Into<Cow<'t, str>>) -> Cow<'t, str> {
+        if self.config.strip_styles {
+            text.into()
+        } else {
+            style.paint(text)
+        }
+    }
+
+    /// Create the row for the rich text line with the given index. The line style hook
+    /// receives the visible text, its prefix is styled with the hook's style, and the spans
+    /// keep their own styles.
+    pub(crate) fn styled_row<'l>(&self, index: usize, line: &StyledLine<'_>) -> Row<'l> {
+        let mut alignment = self.default_alignment();
+        let mut prefix = None;
+        if let Some(hook) = self.config.line_style {
+            let style = hook(index, &line.text());
+            alignment = style.alignment.unwrap_or(alignment);
+            prefix = Some(Span::new(style.prefix, style.style));
+        }
+        self.spans_row(prefix.iter().chain(&line.spans), alignment)
+    }
+
+    /// Create the rows for the given line, wrapping it if the config asks for it.
+    /// The wrapped rows keep the indent of the line.
+    fn rich_rows<'l>(&self, line: &Line<'_>) -> Vec<Row<'l>> {
+        let alignment = line.alignment.unwrap_or_else(|| self.default_alignment());
+        let indent = Span::new(" ".repeat(line.indent * INDENT_WIDTH), Style::new());
+        let text = trim_input(&self.config, &line.text);
+        let pieces = match self.wrap_width() {
+            Some(width) => {
+                let width = width.saturating_sub(indent.text.len());
+                wrap(text, width, self.config.measure())
+            }
+            None => iter::once(0..text.len()).collect(),
+        };
+
+        pieces
+            .into_iter()
+            .map(|range| {
+                let span = Span::new(&text[range], line.text_style());
+                self.spans_row(iter::once(&indent).chain(iter::once(&span)), alignment)
+            })
+            .collect()
+    }
+
+    /// Create the row displaying the given spans in their styles, truncated to the fixed width.
+    fn spans_row<'s, 'l>(
+        &self,
+        spans: impl Iterator<Item = &'s Span<'s>>,
+        alignment: Alignment,
+    ) -> Row<'l> {
+        let measure = self.config.measure();
+        let spans = spans.collect::<Vec<_>>();
+        let mut remaining = self.content_limit();
+        let mut ellipsis = None;
+        if let (Some(limit), Some(marker)) = (&mut remaining, self.config.ellipsis) {
+            let total = spans
+                .iter()
+                .map(|span| measure.width(&span.text))
+                .sum::<usize>();
+            if total > *limit {
+                let marker = measure.truncate(marker, *limit);
+                *limit -= measure.width(marker);
+                ellipsis = Some(marker);
+            }
+        }
+
+        let mut text = String::new();
+        let mut width = 0;
+        for span in spans {
+            let visible = match remaining {
+                Some(remaining) => self.config.measure().truncate(&span.text, remaining),
+                None => &span.text,
+            };
+            if visible.is_empty() {
+                continue;
+            }
+
+            let visible_width = self.config.measure().width(visible);
+            width += visible_width;
+            if let Some(remaining) = &mut remaining {
+                *remaining -= visible_width;
+            }
+            text.push_str(&self.paint(span.style, visible));
+        }
+        if let Some(ellipsis) = ellipsis {
+            width += measure.width(ellipsis);
+            text.push_str(ellipsis);
+        }
+
+        Row::Content {
+            text: text.into(),
+            width,
+            alignment,
+            border_glyph: None,
+        }
+    }
+
+    /// Lay out the content rows of the box, truncating them to `max_width` if given.
+    fn rows<'l>(&self, lines: &'l [String], max_width: Option<usize>) -> Vec<Row<'l>> {
+        let config = &self.config;
+        self.layout(lines.len(), |rows| match config.orientation {
+            Orientation::Rows => self.extend_lines(rows, lines, 0),
+            Orientation::Columns => rows.extend(transpose(lines, config, max_width)),
+        })
+    }
+
+    /// Append the rows of the input lines, the first of which has the given index.
+    fn extend_lines<'l>(&self, rows: &mut Vec<Row<'l>>, lines: &'l [String], first: usize) {
+        if self.wrap_width().is_some() {
+            let lines = map_items(lines, |index, line| self.line_rows(first + index, line));
+            self.extend_spaced(rows, lines)
+        } else {
+            let lines = map_items(lines, |index, line| {
+                iter::once(self.line_row(first + index, line))
+            });
+            self.extend_spaced(rows, lines)
+        }
+    }
+
+    /// Append the rows of the rich text lines.
+    fn extend_styled<'l>(&self, rows: &mut Vec<Row<'l>>, lines: &[StyledLine<'_>]) {
+        if self.wrap_width().is_some() {
+            let lines = map_items(lines, |index, line| self.styled_rows(index, line));
+            self.extend_spaced(rows, lines)
+        } else {
+            let lines = map_items(lines, |index, line| {
+                iter::once(self.styled_row(index, line))
+            });
+            self.extend_spaced(rows, lines)
+        }
+    }
+
+    /// Append the rows of every input line, separated by the blank rows of the line spacing.
+    fn extend_spaced<'l, R>(&self, rows: &mut Vec<Row<'l>>, lines: Vec<R>)
+    where
+        R: IntoIterator<Item = Row<'l>>,
+    {
+        let spacing = self.config.line_spacing;
+        if spacing == 0 {
+            rows.extend(lines.into_iter().flatten());
+            return;
+        }
+
+        let alignment = self.default_alignment();
+        rows.reserve(lines.len() * (spacing + 1));
+        for (index, line) in lines.into_iter().enumerate() {
+            if index > 0 {
+                rows.extend((0..spacing).map(|_| Row::blank(alignment)));
+            }
+            rows.extend(line);
+        }
+    }
+
+    /// Surround the rows pushed by `body` with the timestamp, the footer, the side caption
+    /// and the vertical padding. The body is expected to push about `lines` rows.
+    fn layout<'l>(&self, lines: usize, body: impl FnOnce(&mut Vec<Row<'l>>)) -> Vec<Row<'l>> {
+        let config = &self.config;
+        let alignment = self.default_alignment();
+        let padding = config.vertical_padding;
+
+        let timestamp = config.timestamp.map(|f| f());
+        let footer = self.footer();
+
+        let mut rows = Vec::with_capacity(lines + padding * 2 + 3);
+        rows.extend((0..padding).map(|_| Row::blank(alignment)));
+        if let Some(timestamp) = timestamp {
+            rows.push(self.content_row(timestamp.into(), alignment.opposite()));
+        }
+        body(&mut rows);
+
+        if let Some(side_caption) = config.side_caption {
+            let content_rows = rows.len() + footer.is_some() as usize + padding;
+            let missing_rows = side_caption.chars().count().saturating_sub(content_rows);
+            rows.extend((0..missing_rows).map(|_| Row::blank(alignment)));
+        }
+
+        if let Some(footer) = footer {
+            rows.push(Row::Rule);
+            rows.push(self.content_row(footer.into(), alignment));
+        }
+        rows.extend((0..padding).map(|_| Row::blank(alignment)));
+
+        if let Some(side_caption) = config.side_caption {
+            let content_rows = rows.iter_mut().filter_map(|row| match row {
+                Row::Content { border_glyph, .. } => Some(border_glyph),
+                Row::Rule | Row::Divider { .. } => None,
+            });
+            for (border_glyph, glyph) in content_rows.zip(side_caption.chars()) {
+                *border_glyph = Some(glyph);
+            }
+        }
+
+        rows
+    }
+
+    /// Produce the footer row text if the config has a footer provider.
+    pub(crate) fn footer(&self) -> Option<String> {
+        self.config.footer.map(|f| f().join(FOOTER_FIELD_SEPARATOR))
+    }
+}
+
+/// The rows of a box that only depend on its width.
+#[derive(Debug, Clone)]
+pub(crate) struct Borders {
+    /// The width of the content area.
+    pub(crate) inner_width: usize,
+    pub(crate) top: String,
+    pub(crate) rule: String,
+    pub(crate) bottom: String,
+    /// The caption displayed by the bottom border.
+    pub(crate) caption: Option<String>,
+}
+
+impl Borders {
+    /// Compute the borders of a box with the given content width.
+    /// If the width is `fixed`, the caption is truncated to fit into the box,
+    /// otherwise the box is widened to fit the caption.
+    fn new(
+        config: &TextBoxConfig<'_>,
+        measure: Measure<'_>,
+        inner_width: usize,
+        fixed: bool,
+    ) -> Self {
+        let (caption, inner_width) = fit_caption(config, measure, inner_width, fixed);
+        let border_len = config.horizontal_bar.len() * (inner_width + 2) + 8;
+
+        let mut top = String::with_capacity(border_len);
+        push_top(&mut top, config, inner_width, None);
+        let mut rule = String::with_capacity(border_len);
+        push_rule(&mut rule, config, inner_width);
+        let mut bottom = String::with_capacity(border_len);
+        push_bottom(
+            &mut bottom,
+            config,
+            measure,
+            inner_width,
+            caption.as_deref(),
+            None,
+        );
+
+        Self {
+            inner_width,
+            top,
+            rule,
+            bottom,
+            caption: caption.map(Cow::into_owned),
+        }
+    }
+
+    /// Redraw the top and the bottom borders with the given markers embedded into them.
+    fn with_markers(
+        self,
+        config: &TextBoxConfig<'_>,
+        measure: Measure<'_>,
+        top: Option<&str>,
+        bottom: Option<&str>,
+    ) -> Self {
+        let mut borders = self;
+        if top.is_some() {
+            borders.top.clear();
+            push_top(&mut borders.top, config, borders.inner_width, top);
+        }
+        if bottom.is_some() {
+            borders.bottom.clear();
+            push_bottom(
+                &mut borders.bottom,
+                config,
+                measure,
+                borders.inner_width,
+                borders.caption.as_deref(),
+                bottom,
+            );
+        }
+        borders
+    }
+
+    /// Render a single row between the top and the bottom borders of the box.
+    pub(crate) fn render_row(&self, row: &Row<'_>, config: &TextBoxConfig<'_>) -> String {
+        match row {
+            Row::Content {
+                text,
+                width,
+                alignment,
+                border_glyph,
+            } => render_content(
+                config,
+                (text, *width),
+                self.inner_width,
+                *alignment,
+                *border_glyph,
+            ),
+            Row::Rule => self.rule.clone(),
+            Row::Divider { title, width } => {
+                let mut row = String::with_capacity(self.rule.len() + title.len() + 2);
+                push_divider(&mut row, config, (title, *width), self.inner_width);
+                row
+            }
+        }
+    }
+}
+
+/// Render a content row with the text of the given width in a box with the given content width,
+/// replacing one of the vertical bars with the glyph of the side caption if there's one.
+pub(crate) fn render_content(
+    config: &TextBoxConfig<'_>,
+    (text, width): (&str, usize),
+    inner_width: usize,
+    alignment: Alignment,
+    border_glyph: Option<char>,
+) -> String {
+    let segments = config
+        .border
+        .map(|border| border.segments(BorderRow::Content, 0));
+    let (left, right) = match &segments {
+        Some(segments) => (&*segments.left, &*segments.right),
+        None => glyphs(config, BorderRow::Content),
+    };
+
+    let mut buffer = [0; 4];
+    let glyph = border_glyph.map(|glyph| &*glyph.encode_utf8(&mut buffer));
+    let (left_bar, right) = match (glyph, config.side_caption_border) {
+        (Some(glyph), Side::Left) => (glyph, right),
+        (Some(glyph), Side::Right) => (left, glyph),
+        (None, _) => (left, right),
+    };
+    let bars = (left_bar, right_bar(config, right));
+
+    let mut row = String::with_capacity(text.len() + inner_width - width + 8);
+    let fill = config.padding_fill.filter(|fill| !fill.is_empty());
+    push_content(&mut row, (text, width), inner_width, alignment, bars, fill);
+    row
+}
+
+/// Fit the caption of the config into a box with the given content width, returning
+/// the caption and the resulting content width. See [`Borders::new`].
+fn fit_caption<'c>(
+    config: &TextBoxConfig<'c>,
+    measure: Measure<'_>,
+    inner_width: usize,
+    fixed: bool,
+) -> (Option<Cow<'c, str>>, usize) {
+    let inner_width = match config.min_width {
+        Some(width) if !fixed => inner_width.max(width.saturating_sub(BORDER_WIDTH)),
+        _ => inner_width,
+    };
+    match config.last_line_caption {
+        Some(caption) if fixed || config.truncate_caption => (
+            Some(measure.truncate(caption, inner_width).into()),
+            inner_width,
+        ),
+        Some(caption) => {
+            let width = inner_width.max(measure.width(caption));
+            match max_inner_width(config).map(|limit| limit.max(inner_width)) {
+                Some(limit) if width > limit => {
+                    (Some(measure.truncate(caption, limit).into()), limit)
+                }
+                _ => (Some(caption.into()), width),
+            }
+        }
+        None => (None, inner_width),
+    }
+}
+
+/// The content width of the boxes at the [`max_width`](TextBoxConfig::max_width) of the config.
+fn max_inner_width(config: &TextBoxConfig<'_>) -> Option<usize> {
+    config
+        .max_width
+        .map(|width| width.saturating_sub(BORDER_WIDTH))
+}
+
+/// Append the top border of a box with the given content width, embedding the marker if given.
+pub(crate) fn push_top(
+    out: &mut String,
+    config: &TextBoxConfig<'_>,
+    inner_width: usize,
+    marker: Option<&str>,
+) {
+    let width = inner_width + 2;
+    push_marked_border(out, config, BorderRow::Top, width, (true, true), marker);
+}
+
+/// Append a horizontal rule of a box with the given content width.
+fn push_rule(out: &mut String, config: &TextBoxConfig<'_>, inner_width: usize) {
+    push_border(
+        out,
+        config,
+        BorderRow::Separator,
+        inner_width + 2,
+        (true, true),
+    );
+}
+
+/// Append a horizontal rule of a box with the given content width, with the title of the given
+/// width embedded next to the left piece, or to the right one if the box is mirrored.
+/// The title must already fit into the box.
+fn push_divider(
+    out: &mut String,
+    config: &TextBoxConfig<'_>,
+    (title, width): (&str, usize),
+    inner_width: usize,
+) {
+    let row = BorderRow::Separator;
+    let (before, after) = if config.mirrored {
+        (inner_width - width - 1, 1)
+    } else {
+        (1, inner_width - width - 1)
+    };
+    push_border(out, config, row, before, (true, false));
+    out.push(' ');
+    out.push_str(title);
+    out.push(' ');
+    push_border(out, config, row, after, (false, true));
+}
+
+/// Append the bottom border of a box with the given content width, embedding the marker
+/// if given. The caption must already fit into the box.
+pub(crate) fn push_bottom(
+    out: &mut String,
+    config: &TextBoxConfig<'_>,
+    measure: Measure<'_>,
+    inner_width: usize,
+    caption: Option<&str>,
+    marker: Option<&str>,
+) {
+    let row = BorderRow::Bottom;
+    let (caption, width) = match caption {
+        Some(caption) => (caption, measure.width(caption)),
+        None => return push_marked_border(out, config, row, inner_width + 2, (true, true), marker),
+    };
+
+    let alignment = caption_alignment(config);
+    let fill = inner_width - width;
+    let (left_fill, right_fill) = match alignment {
+        Alignment::Left => (None, Some(fill + 1)),
+        Alignment::Center => (Some(fill / 2), Some(fill - fill / 2)),
+        Alignment::Right => (Some(fill + 1), None),
+    };
+    let marker_left = config.mirrored || right_fill.is_none();
+
+    if let Some(fill) = left_fill {
+        let marker = marker.filter(|_| marker_left);
+        push_marked_border(out, config, row, fill, (true, false), marker);
+    }
+    out.push('<');
+    out.push_str(caption);
+    out.push('>');
+    if let Some(fill) = right_fill {
+        let marker = marker.filter(|_| !marker_left);
+        push_marked_border(out, config, row, fill, (false, true), marker);
+    }
+}
+
+/// The given piece of the right border, or `None` if the box is [ragged](TextBoxConfig::ragged).
+fn right_bar<'b>(config: &TextBoxConfig<'_>, bar: &'b str) -> Option<&'b str> {
+    if config.ragged {
+        None
+    } else {
+        Some(bar)
+    }
+}
+
+/// Append a content row with the text of the given width surrounded by the given pair of bars,
+/// padding it with the spaces or with the given fill pattern.
+/// Without the right bar, the trailing padding is omitted as well.
+fn push_content(
+    out: &mut String,
+    (text, width): (&str, usize),
+    inner_width: usize,
+    alignment: Alignment,
+    (left_bar, right_bar): (&str, Option<&str>),
+    fill: Option<&str>,
+) {
+    let padding = inner_width - width;
+    let left_padding = match alignment {
+        Alignment::Left => 0,
+        Alignment::Center => padding / 2,
+        Alignment::Right => padding,
+    };
+    let push_padding = |out: &mut String, column: usize, count: usize| match fill {
+        Some(fill) => push_pattern(out, fill, column, count),
+        None => push_spaces(out, count),
+    };
+
+    out.push_str(left_bar);
+    out.push(' ');
+    push_padding(out, 0, left_padding);
+    out.push_str(text);
+    match right_bar {
+        Some(right_bar) => {
+            push_padding(out, left_padding + width, padding - left_padding);
+            out.push(' ');
+            out.push_str(right_bar);
+        }
+        None => out.truncate(out.trim_end_matches(' ').len()),
+    }
+}
+
+/// A row between the top and the bottom borders of the box, see [`RenderPlan`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Row<'s> {
+    /// A line of text padded to the width of the box.
+    Content {
+        /// The text of the line, including the escape sequences of its styles.
+        text: Cow<'s, str>,
+        /// The width of the visible text, which must fit into a box with a fixed width.
+        width: usize,
+        alignment: Alignment,
+        /// The character replacing the vertical bar as part of the side caption.
+        border_glyph: Option<char>,
+    },
+    /// A horizontal rule joined with the side borders.
+    Rule,
+    /// A horizontal rule joined with the side borders, with a title embedded.
+    Divider {
+        /// The title embedded into the rule.
+        title: Cow<'s, str>,
+        /// The width of the title, which must fit into a box with a fixed width.
+        width: usize,
+    },
+}
+
+impl<'s> Row<'s> {
+    /// Create an empty content row.
+    pub fn blank(alignment: Alignment) -> Self {
+        Row::Content {
+            text: "".into(),
+            width: 0,
+            alignment,
+            border_glyph: None,
+        }
+    }
+
+    /// The kind of the rendered row.
+    pub fn kind(&self) -> RowKind {
+        match self {
+            Row::Content { .. } => RowKind::Content,
+            Row::Rule | Row::Divider { .. } => RowKind::Separator,
+        }
+    }
+
+    /// The width of the row's content.
+    pub fn width(&self) -> usize {
+        match self {
+            Row::Content { width, .. } => *width,
+            Row::Rule => 0,
+            Row::Divider { width, .. } => width + DIVIDER_MARGIN,
+        }
+    }
+
+    /// Convert the row into one that owns its text.
+    pub fn into_owned(self) -> Row<'static> {
+        match self {
+            Row::Content {
+                text,
+                width,
+                alignment,
+                border_glyph,
+            } => Row::Content {
+                text: Cow::Owned(text.into_owned()),
+                width,
+                alignment,
+                border_glyph,
+            },
+            Row::Rule => Row::Rule,
+            Row::Divider { title, width } => Row::Divider {
+                title: Cow::Owned(title.into_owned()),
+                width,
+            },
+        }
+    }
+}
+
+/// Whether the input line doesn't have any visible characters.
+pub(crate) fn is_blank(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
+/// Clean the input lines with [`split_input`], then collapse the runs of blank lines,
+/// remove the leading and trailing blank lines, and merge the repeated lines
+/// if the config asks for it.
+fn clean_lines<'l>(config: &TextBoxConfig<'_>, lines: &'l [String]) -> Cow<'l, [String]> {
+    match split_input(config, lines) {
+        Cow::Borrowed(lines) => merge_repeats(config, lines),
+        Cow::Owned(lines) => Cow::Owned(merge_repeats(config, &lines).into_owned()),
+    }
+}
+
+/// Split the lines with the embedded line endings, which would otherwise escape the borders,
+/// into separate lines, and clean every line with [`clean_input`]. Every entry point
+/// laying out the input lines passes them through here first.
+fn split_input<'l>(config: &TextBoxConfig<'_>, lines: &'l [String]) -> Cow<'l, [String]> {
+    if !lines
+        .iter()
+        .any(|line| line.contains('\n') || needs_cleaning(config, line))
+    {
+        return Cow::Borrowed(lines);
+    }
+
+    let clean = |line| clean_input(config, line).into_owned();
+    let mut split = Vec::with_capacity(lines.len());
+    for line in lines {
+        match line.contains('\n') {
+            true => split.extend(line.lines().map(clean)),
+            false => split.push(clean(line)),
+        }
+    }
+    Cow::Owned(split)
+}
+
+/// Skip the blank lines and merge the repeated lines if the config asks for it.
+fn merge_repeats<'l>(config: &TextBoxConfig<'_>, lines: &'l [String]) -> Cow<'l, [String]> {
+    let lines = skip_blank_lines(config, lines);
+    let has_repeats = lines
+        .windows(2)
+        .any(|pair| pair[0] == pair[1] && !is_blank(&pair[0]));
+    if !config.dedupe_lines || !has_repeats {
+        return lines;
+    }
+
+    let mut deduped = Vec::with_capacity(lines.len());
+    let mut index = 0;
+    while index < lines.len() {
+        let line = &lines[index];
+        let count = match is_blank(line) {
+            true => 1,
+            false => lines[index..]
+                .iter()
+                .take_while(|next| *next == line)
+                .count(),
+        };
+        deduped.push(repeated_line(config, line, count));
+        index += count;
+    }
+    Cow::Owned(deduped)
+}
+
+/// The line displayed in place of a run of `count` identical lines.
+pub(crate) fn repeated_line(config: &TextBoxConfig<'_>, line: &str, count: usize) -> String {
+    if count == 1 {
+        return line.to_string();
+    }
+    format!("{} {}{}", line, content_glyph(config, REPEATS), count)
+}
+
+/// Collapse the runs of blank lines and remove the leading and trailing blank lines
+/// if the config asks for it.
+fn skip_blank_lines<'l>(config: &TextBoxConfig<'_>, lines: &'l [String]) -> Cow<'l, [String]> {
+    let mut lines = lines;
+    if config.trim_blank_lines {
+        let start = lines.iter().position(|line| !is_blank(line));
+        let end = lines.iter().rposition(|line| !is_blank(line));
+        lines = match (start, end) {
+            (Some(start), Some(end)) => &lines[start..=end],
+            _ => &[],
+        };
+    }
+
+    let has_runs = lines
+        .windows(2)
+        .any(|pair| is_blank(&pair[0]) && is_blank(&pair[1]));
+    if !config.collapse_blank_lines || !has_runs {
+        return Cow::Borrowed(lines);
+    }
+
+    let mut previous_blank = false;
+    let mut collapsed = Vec::with_capacity(lines.len());
+    for line in lines {
+        let blank = is_blank(line);
+        if !(blank && previous_blank) {
+            collapsed.push(line.clone());
+        }
+        previous_blank = blank;
+    }
+    Cow::Owned(collapsed)
+}
+
+/// Whether the text is a number like `-1`, `1,024`, `3.5`, or `97%`, which is right-aligned
+/// within its column if the config [aligns the numbers](TextBoxConfig::align_numbers).
+pub(crate) fn is_numeric(text: &str) -> bool {
+    let text = text.trim();
+    let text = text.strip_suffix('%').unwrap_or(text);
+    let text = text.strip_prefix(['-', '+']).unwrap_or(text);
+    text.starts_with(|c: char| c.is_ascii_digit())
+        && text.ends_with(|c: char| c.is_ascii_digit())
+        && text.matches('.').count() <= 1
+        && text
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '.' | ',' | '_'))
+}
+
+/// Whether the tabs or the control characters of the input line are replaced by the config,
+/// see [`clean_input`].
+fn needs_cleaning(config: &TextBoxConfig<'_>, line: &str) -> bool {
+    let control = |c: char| c.is_control() && c != '\t';
+    (config.tab_width.is_some() && line.contains('\t'))
+        || (config.control_chars != ControlPolicy::Keep && line.contains(control))
+}
+
+/// Expand the tabs and strip or escape the control characters of the input line
+/// if the config asks for it.
+pub(crate) fn clean_input<'l>(config: &TextBoxConfig<'_>, line: &'l str) -> Cow<'l, str> {
+    if !needs_cleaning(config, line) {
+        return Cow::Borrowed(line);
+    }
+
+    let mut line = match config.tab_width {
+        Some(tab_width) => expand_tabs(config.measure(), line, tab_width),
+        None => line.to_string(),
+    };
+    let control = |c: char| c.is_control() && c != '\t';
+    match config.control_chars {
+        ControlPolicy::Keep => {}
+        ControlPolicy::Strip => {
+            line = ansi::visible_runs(&line).map(|(_, run)| run).collect();
+            line.retain(|c| !control(c));
+        }
+        ControlPolicy::Escape => {
+            line = line
+                .chars()
+                .map(|c| match c {
+                    '\r' => String::from("\\r"),
+                    '\0' => String::from("\\0"),
+                    c if control(c) && c.is_ascii() => format!("\\x{:02x}", c as u32),
+                    c if control(c) => c.escape_unicode().to_string(),
+                    c => c.to_string(),
+                })
+                .collect();
+        }
+    }
+    Cow::Owned(line)
+}
+
+/// The width of a glyph of the config, like the column divider, measured with the measurer
+/// of the config if there's one. The default measurer counts the bytes, which is too many
+/// for the box-drawing characters, so then every character takes a column.
+pub(crate) fn glyph_width(config: &TextBoxConfig<'_>, glyph: &str) -> usize {
+    match config.measurer {
+        Some(measurer) => measurer.width(glyph),
+        None => glyph.chars().count(),
+    }
+}
+
+/// The position of the caption within the bottom border, swapped in the mirrored boxes.
+pub(crate) fn caption_alignment(config: &TextBoxConfig<'_>) -> Alignment {
+    if config.mirrored {
+        config.caption_alignment.opposite()
+    } else {
+        config.caption_alignment
+    }
+}
+
+/// Remove the trailing whitespace of the input line if the config asks for it.
+pub(crate) fn trim_input<'l>(config: &TextBoxConfig<'_>, line: &'l str) -> &'l str {
+    if config.trim_trailing_whitespace {
+        line.trim_end_matches([' ', '\t'])
+    } else {
+        line
+    }
+}
+
+/// Turn each of the given lines into a column, placing the columns side by side.
+/// Every cell of the resulting rows is one column wide, holding a grapheme cluster of the
+/// visible text: the escape sequences can't be split across the rows, so they're dropped.
+/// The columns that don't fit into `max_width` are omitted.
+fn transpose<'s>(
+    lines: &[String],
+    config: &TextBoxConfig<'_>,
+    max_width: Option<usize>,
+) -> Vec<Row<'s>> {
+    let separator = format!(" {} ", config.vertical_bar);
+    let max_columns = max_width.map_or(lines.len(), |max_width| max_width.div_ceil(4));
+    let lines = &lines[..lines.len().min(max_columns)];
+
+    let texts = lines
+        .iter()
+        .map(|line| match trim_input(config, line) {
+            line if ansi::find_escape(line).is_none() => Cow::Borrowed(line),
+            line => Cow::Owned(ansi::visible_runs(line).map(|(_, run)| run).collect()),
+        })
+        .collect::<Vec<_>>();
+    let mut columns = texts.iter().map(|text| clusters(text)).collect::<Vec<_>>();
+    let height = columns
+        .iter()
+        .map(|column| column.clone().count())
+        .max()
+        .unwrap_or(0);
+    let width = (columns.len() * 4).saturating_sub(3);
+
+    (0..height)
+        .map(|_| Row::Content {
+            text: columns
+                .iter_mut()
+                .map(|column| column.next().unwrap_or(" "))
+                .collect::<Vec<_>>()
+                .join(&separator)
+                .into(),
+            width,
+            alignment: if config.mirrored {
+                Alignment::Right
+            } else {
+                Alignment::Left
+            },
+            border_glyph: None,
+        })
+        .collect()
+}
+
+/// Split the text into its grapheme clusters.
+fn clusters(text: &str) -> impl Iterator<Item = &str> + Clone {
+    let mut rest = text;
+    iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+        let (cluster, tail) = rest.split_at(grapheme::first_len(rest));
+        rest = tail;
+        Some(cluster)
+    })
+}
+
+/// Apply the given function to every item, in parallel if the `rayon` feature is enabled
+/// and there are enough items to outweigh the synchronization overhead.
+fn map_items<'t, T: Sync, R: Send>(
+    items: &'t [T],
+    f: impl Fn(usize, &'t T) -> R + Sync + Send,
+) -> Vec<R> {
+    #[cfg(feature = "rayon")]
+    {
+        if items.len() >= PARALLEL_THRESHOLD {
+            use rayon::prelude::*;
+            return items
+                .par_iter()
+                .enumerate()
+                .map(|(index, item)| f(index, item))
+                .collect();
+        }
+    }
+    items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| f(index, item))
+        .collect()
+}
+
+/// Find the maximum of the values produced for the given items, or zero if there are no items.
+/// Parallelized in the same way as [`map_items`].
+fn max_item<T: Sync>(items: &[T], f: impl Fn(&T) -> usize + Sync + Send) -> usize {
+    #[cfg(feature = "rayon")]
+    {
+        if items.len() >= PARALLEL_THRESHOLD {
+            use rayon::prelude::*;
+            return items.par_iter().map(f).max().unwrap_or(0);
+        }
+    }
+    items.iter().map(f).max().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{generate_with_config, CharWidth, Color, LineStyle, Style};
+
+    #[test]
+    fn test_fixed_width() {
+        let renderer = Renderer::with_width(TextBoxConfig::dos(), 10);
+        assert_eq!(renderer.width(), Some(10));
+
+        assert_eq!(renderer.render(&[]), "╔════════╗\n╚════════╝");
+        assert_eq!(
+            renderer.render(&["pi = 3.14159".to_string(), "e = 2.71828".to_string()]),
+            "\
+╔════════╗
+║ pi = 3 ║
+║ e = 2. ║
+╚════════╝"
+        );
+
+        let renderer = Renderer::with_width(TextBoxConfig::dos().with_caption("Long caption"), 10);
+        assert_eq!(renderer.render(&[]), "╔════════╗\n<Long c>═╝");
+
+        let renderer = Renderer::with_width(TextBoxConfig::default().columns(), 13);
+        assert_eq!(
+            renderer.render(&["ab".to_string(), "cd".to_string(), "ef".to_string()]),
+            "\
+╭───────────╮
+│ a │ c │ e │
+│ b │ d │ f │
+╰───────────╯"
+        );
+        assert_eq!(
+            renderer.render(&[
+                "ab".to_string(),
+                "cd".to_string(),
+                "ef".to_string(),
+                "gh".to_string()
+            ]),
+            renderer.render(&["ab".to_string(), "cd".to_string(), "ef".to_string()]),
+        );
+    }
+
+    #[test]
+    fn test_small_input_fast_path() {
+        let lines = (0..SMALL_INPUT_LINES)
+            .map(|i| "ab".repeat(i))
+            .collect::<Vec<_>>();
+        let configs = [
+            TextBoxConfig::default(),
+            TextBoxConfig::dos().with_caption("A caption longer than lines"),
+            TextBoxConfig::default()
+                .with_caption("Mirrored")
+                .mirrored()
+                .with_tail(3),
+        ];
+
+        for config in &configs {
+            for renderer in &[Renderer::new(*config), Renderer::with_width(*config, 12)] {
+                assert!(renderer.is_plain());
+                for len in 0..=lines.len() {
+                    let lines = &lines[..len];
+                    assert_eq!(renderer.render_small(lines), renderer.render_rows(lines));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_renderer_reuse() {
+        let timestamp = || "now".to_string();
+        let renderer = Renderer::new(TextBoxConfig::default().with_timestamp(&timestamp));
+
+        for lines in &[vec![], vec!["a".to_string()], vec!["abcdef".to_string()]] {
+            assert_eq!(
+                renderer.render(lines),
+                generate_with_config(lines, *renderer.config())
+            );
+        }
+        assert_eq!(renderer.width(), None);
+    }
+
+    #[test]
+    fn test_styled_lines() {
+        let red = Style::new().fg(Color::Red);
+        let line = StyledLine::new()
+            .with_span("ab", red)
+            .with_span("", Style::new().bold())
+            .with_span("cd", Style::new());
+
+        let renderer = Renderer::new(TextBoxConfig::default());
+        assert_eq!(
+            renderer.render_styled(std::slice::from_ref(&line)),
+            "\
+╭──────╮
+│ \x1b[31mab\x1b[0mcd │
+╰──────╯"
+        );
+
+        let renderer = Renderer::with_width(TextBoxConfig::default(), 7);
+        assert_eq!(
+            renderer.render_styled(&[line.clone(), StyledLine::from("x")]),
+            "\
+╭─────╮
+│ \x1b[31mab\x1b[0mc │
+│ x   │
+╰─────╯"
+        );
+
+        let hook = |_: usize, text: &str| {
+            assert_eq!(text, "abcd");
+            LineStyle::new()
+                .with_prefix("> ")
+                .with_style(Style::new().dim())
+                .with_alignment(Alignment::Right)
+        };
+        let renderer = Renderer::with_width(TextBoxConfig::default().with_line_style(&hook), 12);
+        let mut stream = renderer.stream(Vec::new()).unwrap();
+        stream.write_styled(&line).unwrap();
+        let streamed = String::from_utf8(stream.finish().unwrap()).unwrap();
+        assert_eq!(streamed, renderer.render_styled(&[line]));
+        assert_eq!(
+            streamed,
+            "\
+╭──────────╮
+│   \x1b[2m> \x1b[0m\x1b[31mab\x1b[0mcd │
+╰──────────╯"
+        );
+    }
+
+    #[test]
+    fn test_is_numeric() {
+        for number in ["0", "-12", "+3.25", "1,024", "10_000", "97%", " 5 "].iter() {
+            assert!(is_numeric(number), "{}", number);
+        }
+        for text in ["", "-", "1.", ".5", "1.2.3", "12 KiB", "v1", "%"].iter() {
+            assert!(!is_numeric(text), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let renderer = Renderer::snapshot(TextBoxConfig::dos().with_markup().with_tail(0), 9);
+        let line = StyledLine::new().with_span("x", Style::new().fg(Color::Red));
+
+        assert_eq!(
+            renderer.render(&["[red]abc[/red] def".to_string()]),
+            "+-------+\n| abc d |\n+-------+\n`->"
+        );
+        assert_eq!(
+            renderer.render_styled(&[line]),
+            "+-------+\n| x     |\n+-------+\n`->"
+        );
+    }
+
+    #[test]
+    fn test_render_json() {
+        let config = TextBoxConfig {
+            tail_column: Some(1),
+            ..TextBoxConfig::default()
+        };
+        let json = Renderer::new(config).render_json(&[String::from("a\"b")]);
+
+        assert_eq!(
+            json,
+            concat!(
+                r#"{"width":7,"height":4,"caption":null,"rows":["#,
+                r#"{"kind":"border","text":"╭─────╮"},"#,
+                r#"{"kind":"content","text":"│ a\"b │","content":"a\"b","alignment":"left"},"#,
+                r#"{"kind":"border","text":"╰─────╯"},"#,
+                r#"{"kind":"tail","text":" ╰─▸"}]}"#,
+            )
+        );
+
+        let config = TextBoxConfig::ascii().with_caption("cap");
+        let has_position = |config, position| {
+            let json = Renderer::new(config).render_json(&[String::from("a long line")]);
+            json.contains(&format!(
+                r#""caption":{{"text":"cap","position":"{}"}}"#,
+                position
+            ))
+        };
+        assert!(has_position(config, "left"));
+        assert!(has_position(config.mirrored(), "right"));
+        let centered = config.with_caption_alignment(Alignment::Center);
+        assert!(has_position(centered, "center"));
+        assert!(has_position(centered.mirrored(), "center"));
+        let right = config.with_caption_alignment(Alignment::Right);
+        assert!(has_position(right, "right"));
+        assert!(has_position(right.mirrored(), "left"));
+    }
+
+    #[test]
+    fn test_wrap_overflow() {
+        let hook = |_: usize, _: &str| LineStyle::new().with_prefix("> ");
+        let config = TextBoxConfig::ascii()
+            .with_overflow(OverflowPolicy::Wrap)
+            .with_line_style(&hook);
+        let renderer = Renderer::with_width(config, 12);
+        let lines = ["the quick brown fox".to_string(), "ok".to_string()];
+
+        assert_eq!(
+            renderer.render(&lines),
+            "\
++----------+
+| > the    |
+| > quick  |
+| > brown  |
+| > fox    |
+| > ok     |
++----------+"
+        );
+
+        let renderer = Renderer::classic(TextBoxConfig::ascii());
+        let msg = renderer.render(&["word ".repeat(20)]);
+        let rows = msg.lines().collect::<Vec<_>>();
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows[2], format!("| {:76} |", "word ".repeat(5).trim_end()));
+    }
+
+    #[test]
+    fn test_truncate_caption() {
+        let config = TextBoxConfig::ascii()
+            .with_caption("A verbose caption")
+            .truncate_caption();
+        let lines = ["abc".to_string()];
+        let expected = "\
++-----+
+| abc |
+<A v>-+";
+
+        assert_eq!(Renderer::new(config).render(&lines), expected);
+        let spaced = Renderer::new(config.with_line_spacing(1));
+        assert_eq!(spaced.render(&lines), expected);
+        assert_eq!(Renderer::new(config).measure(&lines), 7);
+    }
+
+    #[test]
+    fn test_render_pages() {
+        let config = TextBoxConfig::ascii().with_caption("{page}/{pages}");
+        let lines = ["a", "b", "c"]
+            .iter()
+            .map(|l| l.to_string())
+            .collect::<Vec<_>>();
+
+        let pages = Renderer::with_width(config, 8).render_pages(&lines, 2);
+        assert_eq!(
+            pages,
+            [
+                "+------+\n| a    |\n| b    |\n<1/2>--+",
+                "+------+\n| c    |\n<2/2>--+"
+            ]
+        );
+
+        let pages = Renderer::new(config).render_pages(&[], 0);
+        assert_eq!(pages, ["+-----+\n<1/1>-+"]);
+    }
+
+    #[test]
+    fn test_padding_fill() {
+        let config = TextBoxConfig::ascii().with_padding_fill("ab");
+        let lines = ["x".to_string(), "12345".to_string()];
+        assert_eq!(
+            generate_with_config(&lines, config.mirrored()),
+            "\
++-------+
+| ababx |
+| 12345 |
++-------+"
+        );
+
+        let ragged = config.ragged();
+        assert_eq!(
+            generate_with_config(&lines, ragged),
+            "+-------\n| x\n| 12345\n+-------"
+        );
+    }
+
+    #[test]
+    fn test_render_lines() {
+        let config = TextBoxConfig::ascii().with_overflow(OverflowPolicy::Wrap);
+        let lines = [
+            Line::new("Report").highlighted(),
+            Line::new("all services are up").with_indent(2),
+            Line::new("ok").with_alignment(Alignment::Right),
+        ];
+
+        assert_eq!(
+            Renderer::with_width(config, 14).render_lines(&lines),
+            "\
++------------+
+| \x1b[1mReport\x1b[0m     |
+|     all    |
+|     servic |
+|     es are |
+|     up     |
+|         ok |
++------------+"
+        );
+    }
+
+    #[test]
+    fn test_render_viewport() {
+        let lines = ["a", "bb", "a much longer line", "c"]
+            .iter()
+            .map(|line| line.to_string())
+            .collect::<Vec<_>>();
+        let config = TextBoxConfig::ascii().with_caption("Log");
+        let renderer = Renderer::new(config);
+
+        let first = renderer.render_viewport(&lines, 0, 2);
+        assert_eq!(
+            first,
+            "\
++--------------------+
+| a                  |
+| bb                 |
+<Log>------- v more -+"
+        );
+        let last = renderer.render_viewport(&lines, 3, 2);
+        assert_eq!(
+            last,
+            "\
++----------- ^ more -+
+| c                  |
+|                    |
+<Log>----------------+"
+        );
+
+        let mirrored = Renderer::new(config.mirrored()).render_viewport(&lines, 1, 1);
+        assert!(mirrored.starts_with("+- ^ more -"));
+        assert!(mirrored.ends_with("+- v more -------<Log>"));
+
+        let all = renderer.render_viewport(&lines, 0, 10);
+        assert_eq!(all.lines().count(), 12);
+        assert!(!all.contains("more"));
+        assert_eq!(renderer.render_viewport(&lines, 10, 1).lines().count(), 3);
+
+        let columns = Renderer::new(TextBoxConfig::ascii().columns());
+        assert_eq!(
+            columns.render_viewport(&lines, 1, 2),
+            "\
++------ ^ more -+
+|   | b |   |   |
+|   |   | m |   |
++------ v more -+"
+        );
+        let narrow = Renderer::new(TextBoxConfig::ascii().columns().with_max_width(13));
+        let viewport = narrow.render_viewport(&lines, 0, 2);
+        assert!(viewport.lines().all(|row| row.len() <= 13), "{}", viewport);
+    }
+
+    #[test]
+    fn test_dedupe_lines() {
+        let lines = ["retrying", "retrying", "retrying", "", "", "ok", "retrying"]
+            .iter()
+            .map(|line| line.to_string())
+            .collect::<Vec<_>>();
+        let renderer = Renderer::new(TextBoxConfig::default().dedupe_lines());
+
+        assert_eq!(
+            renderer.render(&lines),
+            "\
+╭─────────────╮
+│ retrying x3 │
+│             │
+│             │
+│ ok          │
+│ retrying    │
+╰─────────────╯"
+        );
+
+        let config = TextBoxConfig::default()
+            .with_measurer(&CharWidth)
+            .dedupe_lines();
+        let msg = Renderer::new(config).render(&lines[..2]);
+        assert_eq!(msg.lines().nth(1), Some("│ retrying ×2 │"));
+    }
+
+    #[test]
+    fn test_tab_width() {
+        let lines = [String::from("a\tb\nab\tc")];
+        let renderer = Renderer::new(TextBoxConfig::ascii().with_tab_width(4));
+        assert_eq!(
+            renderer.render(&lines),
+            "+-------+\n| a   b |\n| ab  c |\n+-------+"
+        );
+        assert_eq!(renderer.measure(&lines), 9);
+
+        let expected = renderer.render(&lines);
+        assert_eq!(renderer.render_separated(&[&lines]), expected);
+        assert_eq!(renderer.render_viewport(&lines, 0, 2), expected);
+        assert_eq!(
+            renderer.render_split(&lines, &[]),
+            "+----------+\n| a   b |  |\n| ab  c |  |\n+----------+"
+        );
+    }
+
+    #[test]
+    fn test_line_ending() {
+        let config = TextBoxConfig::ascii()
+            .with_line_ending("\r\n")
+            .trailing_newline();
+        let lines = [String::from("a")];
+        let expected = "+---+\r\n| a |\r\n+---+\r\n";
+        assert_eq!(Renderer::new(config).render(&lines), expected);
+        let rows = Renderer::new(config.with_vertical_padding(1).with_tail(1)).render(&lines);
+        assert_eq!(
+            rows,
+            "+---+\r\n|   |\r\n| a |\r\n|   |\r\n+---+\r\n `->\r\n"
+        );
+        let sections = Renderer::new(config).render_separated(&[&lines, &lines]);
+        assert_eq!(sections, "+---+\r\n| a |\r\n+---+\r\n| a |\r\n+---+\r\n");
+    }
+
+    #[test]
+    fn test_control_chars() {
+        let config = TextBoxConfig::ascii().with_tab_width(2);
+        let lines = [String::from("a\tb\x07\u{85}\x1b]8;;x\x1b\\c")];
+        let render = |policy| Renderer::new(config.with_control_chars(policy)).render(&lines);
+        assert_eq!(render(ControlPolicy::Strip), "+------+\n| a bc |\n+------+");
+        assert_eq!(
+            render(ControlPolicy::Escape).lines().nth(1),
+            Some(r"| a b\x07\u{85}\x1b]8;;x\x1b\c |")
+        );
+        assert_eq!(
+            render(ControlPolicy::Keep),
+            Renderer::new(config).render(&lines)
+        );
+
+        let renderer = Renderer::new(config.with_control_chars(ControlPolicy::Strip));
+        let expected = "+------+\n| a bc |\n+------+";
+        assert_eq!(renderer.render_separated(&[&lines]), expected);
+        assert_eq!(renderer.render_viewport(&lines, 0, 1), expected);
+        assert_eq!(
+            renderer.render_split(&lines, &[String::from("\r")]),
+            "+---------+\n| a bc |  |\n+---------+"
+        );
+        assert_eq!(
+            renderer.render_sections(&[Section::new("\x1b[1mt", &lines[..])]),
+            "+------+\n| v t  |\n| a bc |\n+------+"
+        );
+        assert_eq!(
+            crate::BoxBuilder::with_config(*renderer.config())
+                .section("\x1b[1mt\0")
+                .line(lines[0].as_str())
+                .build(),
+            "+------+\n+- t --+\n| a bc |\n+------+"
+        );
+    }
+
+    #[test]
+    fn test_embedded_newlines() {
+        let renderer = Renderer::new(TextBoxConfig::ascii().dedupe_lines());
+        let lines = [
+            String::from("a\nb\r\n"),
+            String::new(),
+            String::from("b\nb"),
+        ];
+        assert_eq!(
+            renderer.render(&lines),
+            "+------+\n| a    |\n| b    |\n|      |\n| b x2 |\n+------+"
+        );
+        assert_eq!(renderer.measure(&lines), 8);
+    }
+
+    #[test]
+    fn test_embedded_newlines_groups() {
+        let renderer = Renderer::new(TextBoxConfig::ascii());
+        let lines = [String::from("a\nbbb")];
+        let expected = "+-----+\n| a   |\n| bbb |\n+-----+";
+        assert_eq!(renderer.render_separated(&[&lines]), expected);
+        assert_eq!(renderer.render_viewport(&lines, 0, 2), expected);
+        assert_eq!(
+            renderer.render_split(&lines, &[String::from("c")]),
+            "+---------+\n| a   | c |\n| bbb |   |\n+---------+"
+        );
+        assert_eq!(
+            renderer.render_sections(&[Section::new("s\nt", &lines[..])]),
+            "+-----+\n| v s |\n| t   |\n| a   |\n| bbb |\n+-----+"
+        );
+        assert_eq!(
+            crate::BoxBuilder::with_config(TextBoxConfig::ascii())
+                .line("a\nbbb")
+                .divider()
+                .build(),
+            "+-----+\n| a   |\n| bbb |\n+-----+\n+-----+"
+        );
+    }
+
+    #[test]
+    fn test_max_width() {
+        let lines = [
+            "the quick brown fox jumps over the lazy dog".to_string(),
+            "short".to_string(),
+        ];
+        let config = TextBoxConfig::ascii().with_max_width(20);
+        let msg = Renderer::new(config).render(&lines);
+        assert_eq!(
+            msg,
+            "\
++-----------------+
+| the quick brown |
+| fox jumps over  |
+| the lazy dog    |
+| short           |
++-----------------+"
+        );
+        assert!(msg.lines().all(|row| row.len() <= 20));
+
+        let narrow = Renderer::new(config).render(&["tiny".to_string()]);
+        assert_eq!(narrow, "+------+\n| tiny |\n+------+");
+
+        let caption = config.with_caption("a caption that is too long");
+        let msg = Renderer::new(caption).render(&["tiny".to_string()]);
+        assert_eq!(msg.lines().last(), Some("<a caption that i>-+"));
+    }
+
+    #[test]
+    fn test_overflow_policies() {
+        let lines = ["a line that is too long".to_string(), "ok".to_string()];
+        let config = TextBoxConfig::ascii().with_ellipsis("...");
+
+        let truncated = Renderer::with_width(config, 14).render(&lines);
+        assert_eq!(
+            truncated,
+            "\
++------------+
+| a line ... |
+| ok         |
++------------+"
+        );
+        let styled = [StyledLine::new().with_span("a line that is too long", Style::new())];
+        let msg = Renderer::with_width(config.strip_styles(), 14).render_styled(&styled);
+        assert_eq!(msg.lines().nth(1), Some("| a line ... |"));
+
+        let expand = config.with_overflow(OverflowPolicy::Expand);
+        let renderer = Renderer::with_width(expand, 14);
+        let expanded = renderer.render(&lines);
+        assert_eq!(expanded, generate_with_config(&lines, config));
+        assert_eq!(renderer.measure(&lines), 27);
+        assert_eq!(
+            renderer.render(&lines[1..]),
+            "+------------+\n| ok         |\n+------------+"
+        );
+        assert_eq!(renderer.measure(&lines[1..]), 14);
+    }
+
+    #[test]
+    fn test_config_width() {
+        let config = TextBoxConfig::ascii().with_width(8);
+        let lines = ["a".to_string(), "too long".to_string()];
+        let expected = "+------+\n| a    |\n| too  |\n+------+";
+        assert_eq!(generate_with_config(&lines, config), expected);
+        assert_eq!(Renderer::new(config).width(), Some(8));
+        assert_eq!(Renderer::with_width(config, 6).width(), Some(6));
+
+        let boxes = [&lines[..1], &lines[..]]
+            .iter()
+            .map(|lines| generate_with_config(lines, config))
+            .collect::<Vec<_>>();
+        assert!(boxes
+            .iter()
+            .flat_map(|msg| msg.lines())
+            .all(|row| row.len() == 8));
+    }
+
+    #[test]
+    fn test_min_width() {
+        let config = TextBoxConfig::ascii().with_min_width(10);
+        let lines = ["ok".to_string()];
+        let expected = "+--------+\n| ok     |\n+--------+";
+        assert_eq!(generate_with_config(&lines, config), expected);
+        assert_eq!(Renderer::new(config).measure(&lines), 10);
+
+        let captioned = config.with_caption("build");
+        assert_eq!(
+            generate_with_config(&lines, captioned),
+            "+--------+\n| ok     |\n<build>--+"
+        );
+        let bounded = config.with_max_width(6).with_overflow(OverflowPolicy::Wrap);
+        assert_eq!(Renderer::new(bounded).measure(&lines), 10);
+        assert_eq!(Renderer::with_width(config, 6).measure(&lines), 6);
+        assert_eq!(
+            generate_with_config(&lines, TextBoxConfig::default()),
+            generate_with_config(&lines, TextBoxConfig::default().with_min_width(3))
+        );
+    }
+
+    #[test]
+    fn test_config_alignment() {
+        let lines = ["a".to_string(), "abc".to_string()];
+        let config = TextBoxConfig::ascii().with_alignment(Alignment::Right);
+        let expected = "+-----+\n|   a |\n| abc |\n+-----+";
+        assert_eq!(generate_with_config(&lines, config), expected);
+        let mirrored = generate_with_config(&lines, config.mirrored());
+        assert_eq!(mirrored.lines().nth(1), Some("| a   |"));
+
+        let style = |index: usize, _: &str| match index {
+            0 => LineStyle::new().with_alignment(Alignment::Left),
+            _ => LineStyle::new(),
+        };
+        let overridden = config.with_line_style(&style);
+        let expected = "+-----+\n| a   |\n| abc |\n+-----+";
+        assert_eq!(generate_with_config(&lines, overridden), expected);
+    }
+
+    #[test]
+    fn test_vertical_padding() {
+        let lines = ["ok".to_string()];
+        let config = TextBoxConfig::ascii().with_vertical_padding(2);
+        let expected = "+----+\n|    |\n|    |\n| ok |\n|    |\n|    |\n+----+";
+        assert_eq!(generate_with_config(&lines, config), expected);
+
+        let footer = || vec!["f".to_string()];
+        let msg =
+            generate_with_config(&lines, config.with_vertical_padding(1).with_footer(&footer));
+        assert_eq!(
+            msg,
+            "+----+\n|    |\n| ok |\n+----+\n| f  |\n|    |\n+----+"
+        );
+
+        let side = config
+            .with_vertical_padding(1)
+            .with_side_caption("abcd", Side::Right);
+        let msg = generate_with_config(&lines, side);
+        assert_eq!(msg.lines().count(), 6);
+    }
+
+    #[test]
+    fn test_caption_alignment() {
+        let lines = ["abcdef".to_string()];
+        let config = TextBoxConfig::ascii().with_caption("c");
+        let bottom = |config: TextBoxConfig<'_>| {
+            let msg = generate_with_config(&lines, config);
+            msg.lines().last().unwrap().to_string()
+        };
+        assert_eq!(bottom(config), "<c>------+");
+        assert_eq!(
+            bottom(config.with_caption_alignment(Alignment::Center)),
+            "+--<c>---+"
+        );
+        assert_eq!(
+            bottom(config.with_caption_alignment(Alignment::Right)),
+            "+------<c>"
+        );
+        assert_eq!(bottom(config.mirrored()), "+------<c>");
+        let mirrored = config.mirrored().with_caption_alignment(Alignment::Right);
+        assert_eq!(bottom(mirrored), "<c>------+");
+
+        let lines = ["abcdefgh"; 4].map(String::from);
+        let viewport = Renderer::with_width(config.with_caption_alignment(Alignment::Center), 22);
+        let msg = viewport.render_viewport(&lines, 0, 2);
+        assert_eq!(msg.lines().last(), Some("+--------<c> v more -+"));
+    }
+
+    #[test]
+    fn test_try_render() {
+        let lines = ["abc".to_string()];
+        let config = TextBoxConfig::ascii().with_caption("caption");
+        let renderer = Renderer::new(config);
+        assert_eq!(renderer.try_render(&lines), Ok(renderer.render(&lines)));
+        assert_eq!(
+            Renderer::new(TextBoxConfig::ascii()).try_render(&[]),
+            Ok("+--+\n+--+".to_string())
+        );
+
+        let overflow = CaptionOverflow {
+            caption_width: 7,
+            available_width: 3,
+        };
+        let truncated = Renderer::new(config.truncate_caption());
+        assert_eq!(truncated.try_render(&lines), Err(overflow.clone()));
+        let limited = Renderer::new(config.with_max_width(7));
+        assert_eq!(limited.try_render(&lines), Err(overflow));
+        let fixed = Renderer::with_width(config, 11);
+        assert_eq!(fixed.try_render(&lines), Ok(fixed.render(&lines)));
+
+        let expanded = Renderer::with_width(config.with_overflow(OverflowPolicy::Expand), 6);
+        let msg = expanded.try_render(&lines).unwrap();
+        assert_eq!(msg, "+---------+\n| abc     |\n<caption>-+");
+        assert_eq!(expanded.measure(&lines), 11);
+    }
+
+    #[test]
+    fn test_render_divided() {
+        let groups = [
+            (None, vec!["a".to_string()]),
+            (None, vec!["b".to_string()]),
+            (Some("Opts"), vec!["c".to_string()]),
+        ];
+        let config = TextBoxConfig::ascii();
+        assert_eq!(
+            Renderer::new(config).render_divided(&groups),
+            "\
++--------+
+| a      |
++--------+
+| b      |
++- Opts -+
+| c      |
++--------+"
+        );
+        let long = [(
+            Some("Opts"),
+            vec!["c".to_string(), "longer line".to_string()],
+        )];
+        let captioned = config.with_caption("Log");
+        assert_eq!(
+            Renderer::new(captioned).render_divided(&long),
+            "\
++-------------+
++- Opts ------+
+| c           |
+| longer line |
+<Log>---------+"
+        );
+        let mirrored = Renderer::new(captioned.mirrored()).render_divided(&long);
+        assert_eq!(
+            mirrored,
+            "\
++-------------+
++------ Opts -+
+|           c |
+| longer line |
++---------<Log>"
+        );
+
+        let fixed = Renderer::with_width(config, 8).render_divided(&groups[2..]);
+        assert_eq!(fixed, "+------+\n+- Op -+\n| c    |\n+------+");
+    }
+}
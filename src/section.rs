@@ -0,0 +1,100 @@
+//! The sections of the reports that can be collapsed, see [`Renderer::render_sections`](crate::Renderer::render_sections).
+use std::borrow::Cow;
+
+use crate::{charset::content_glyph, TextBoxConfig};
+
+/// The markers of the headings of the collapsed and the expanded sections, with their ASCII fallbacks.
+const COLLAPSED: [&str; 2] = ["▸", ">"];
+const EXPANDED: [&str; 2] = ["▾", "v"];
+
+/// A titled group of lines that can be collapsed into its heading row.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// let lines = vec![String::from("12 passed")];
+/// let section = Section::new("Tests", lines).collapsed();
+/// assert!(section.collapsed);
+/// assert_eq!(section.lines.len(), 1);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Section<'t> {
+    /// The title displayed by the heading row of the section.
+    pub title: Cow<'t, str>,
+    /// The lines displayed under the heading unless the section is collapsed.
+    pub lines: Cow<'t, [String]>,
+    /// Whether only the heading is displayed, with the number of the hidden lines.
+    pub collapsed: bool,
+}
+
+impl<'t> Section<'t> {
+    /// Create an expanded section with the given title and lines.
+    pub fn new(title: impl Into<Cow<'t, str>>, lines: impl Into<Cow<'t, [String]>>) -> Self {
+        Self {
+            title: title.into(),
+            lines: lines.into(),
+            collapsed: false,
+        }
+    }
+
+    /// Collapse the section into its heading row.
+    pub fn collapsed(self) -> Self {
+        Self {
+            collapsed: true,
+            ..self
+        }
+    }
+
+    /// Whether the lines are hidden, unless the config expands every section.
+    pub(crate) fn is_hidden(&self, config: &TextBoxConfig<'_>) -> bool {
+        self.collapsed && !config.expand_sections
+    }
+
+    /// The heading row of the section, with the number of the hidden lines if it's collapsed.
+    /// The markers fall back to ASCII unless the measurer of the config counts them as one column.
+    pub(crate) fn heading(&self, config: &TextBoxConfig<'_>) -> String {
+        let marker = |markers| content_glyph(config, markers);
+        if !self.is_hidden(config) {
+            return format!("{} {}", marker(EXPANDED), self.title);
+        }
+        let count = self.lines.len();
+        let noun = if count == 1 { "line" } else { "lines" };
+        format!(
+            "{} {} ({} {} hidden)",
+            marker(COLLAPSED),
+            self.title,
+            count,
+            noun
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CharWidth;
+
+    #[test]
+    fn test_heading() {
+        let lines = ["a".to_string()];
+        let section = Section::new("Build", &lines[..]);
+        let config = TextBoxConfig::default().with_measurer(&CharWidth);
+        assert_eq!(section.heading(&config), "▾ Build");
+        assert_eq!(
+            section.clone().collapsed().heading(&config),
+            "▸ Build (1 line hidden)"
+        );
+        assert_eq!(
+            Section::new("Empty", Vec::new())
+                .collapsed()
+                .heading(&config),
+            "▸ Empty (0 lines hidden)"
+        );
+
+        let collapsed = section.collapsed();
+        let ascii = TextBoxConfig::ascii().with_measurer(&CharWidth);
+        assert_eq!(collapsed.heading(&ascii), "> Build (1 line hidden)");
+        let bytes = TextBoxConfig::default();
+        assert_eq!(collapsed.heading(&bytes), "> Build (1 line hidden)");
+        assert_eq!(collapsed.heading(&config.expand_sections()), "▾ Build");
+    }
+}
@@ -0,0 +1,92 @@
+//! The lazily produced input lines, see [`Renderer::render_source`](crate::Renderer::render_source).
+use std::io;
+
+/// A source of the input lines that are produced one at a time, like the lines of a file.
+///
+/// Implemented for the slices of strings, the [`io::Lines`] of a [`BufRead`](io::BufRead),
+/// and any iterator of strings wrapped into [`IterSource`].
+pub trait LineSource {
+    /// Produce the next line, or `None` if there are no lines left.
+    fn next_line(&mut self) -> io::Result<Option<String>>;
+
+    /// The number of the lines left, if it's known in advance.
+    fn size_hint(&self) -> Option<usize> {
+        None
+    }
+}
+
+impl<S: AsRef<str>> LineSource for &[S] {
+    fn next_line(&mut self) -> io::Result<Option<String>> {
+        Ok(self.split_first().map(|(line, rest)| {
+            *self = rest;
+            line.as_ref().to_string()
+        }))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<B: io::BufRead> LineSource for io::Lines<B> {
+    fn next_line(&mut self) -> io::Result<Option<String>> {
+        self.next().transpose()
+    }
+}
+
+/// A [`LineSource`] producing the items of an iterator.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// let lines = IterSource((1..=3).map(|n| format!("step {}", n)));
+/// let msg = Renderer::new(TextBoxConfig::default()).render_source(lines)?;
+///
+/// assert_eq!(msg, "\
+/// ╭────────╮
+/// │ step 1 │
+/// │ step 2 │
+/// │ step 3 │
+/// ╰────────╯");
+/// # std::io::Result::Ok(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct IterSource<I>(pub I);
+
+impl<I: Iterator> LineSource for IterSource<I>
+where
+    I::Item: Into<String>,
+{
+    fn next_line(&mut self) -> io::Result<Option<String>> {
+        Ok(self.0.next().map(Into::into))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        match self.0.size_hint() {
+            (lower, Some(upper)) if lower == upper => Some(lower),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use io::BufRead;
+
+    #[test]
+    fn test_sources() {
+        let mut slice: &[&str] = &["a", "b"];
+        assert_eq!(slice.size_hint(), Some(2));
+        assert_eq!(slice.next_line().unwrap().as_deref(), Some("a"));
+        assert_eq!(slice.size_hint(), Some(1));
+
+        let mut lines = io::Cursor::new("a\r\nb\n").lines();
+        assert_eq!(lines.next_line().unwrap().as_deref(), Some("a"));
+        assert_eq!(lines.next_line().unwrap().as_deref(), Some("b"));
+        assert_eq!(lines.next_line().unwrap(), None);
+
+        let iter = IterSource(vec!["a"].into_iter());
+        assert_eq!(iter.size_hint(), Some(1));
+        assert_eq!(IterSource((0..).map(|n| n.to_string())).size_hint(), None);
+    }
+}
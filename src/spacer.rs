@@ -0,0 +1,80 @@
+//! Growable runs of spaces and bars that are sliced instead of allocated for every row.
+use std::cell::RefCell;
+
+thread_local! {
+    static SPACER: RefCell<Spacer> = RefCell::new(Spacer::default());
+}
+
+/// The runs of repeated strings cached on the current thread.
+#[derive(Default)]
+struct Spacer {
+    spaces: Run,
+    bars: Run,
+}
+
+/// A buffer holding a string repeated some number of times.
+#[derive(Default)]
+struct Run {
+    unit: String,
+    buffer: String,
+}
+
+impl Run {
+    /// Get the given string repeated `count` times, growing the buffer if needed.
+    fn get(&mut self, unit: &str, count: usize) -> &str {
+        if self.unit != unit {
+            self.unit.clear();
+            self.unit.push_str(unit);
+            self.buffer.clear();
+        }
+
+        let len = unit.len() * count;
+        if self.buffer.len() < len {
+            self.buffer.reserve(len - self.buffer.len());
+            while self.buffer.len() < len {
+                self.buffer.push_str(unit);
+            }
+        }
+
+        &self.buffer[..len]
+    }
+}
+
+/// Append `count` spaces to the given string.
+pub(crate) fn push_spaces(out: &mut String, count: usize) {
+    SPACER.with(|spacer| out.push_str(spacer.borrow_mut().spaces.get(" ", count)));
+}
+
+/// Append `count` copies of the given bar to the given string.
+pub(crate) fn push_bars(out: &mut String, bar: &str, count: usize) {
+    SPACER.with(|spacer| out.push_str(spacer.borrow_mut().bars.get(bar, count)));
+}
+
+/// Append `count` characters of the pattern repeated from its start, beginning with the
+/// character at the given column, so that the pattern lines up across the rows.
+pub(crate) fn push_pattern(out: &mut String, pattern: &str, column: usize, count: usize) {
+    let len = pattern.chars().count();
+    out.extend(pattern.chars().cycle().skip(column % len).take(count));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_runs() {
+        let mut out = String::new();
+        push_spaces(&mut out, 3);
+        push_bars(&mut out, "─", 2);
+        push_spaces(&mut out, 1);
+        push_bars(&mut out, "═", 4);
+        push_bars(&mut out, "═", 0);
+        push_spaces(&mut out, 0);
+        assert_eq!(out, "   ── ════");
+
+        let mut run = Run::default();
+        assert_eq!(run.get("ab", 3), "ababab");
+        assert_eq!(run.get("ab", 1), "ab");
+        assert_eq!(run.get("c", 2), "cc");
+    }
+}
@@ -0,0 +1,511 @@
+//! Incremental rendering of fixed-width boxes.
+use std::{borrow::Cow, io, str::Chars};
+
+use crate::{
+    renderer::{clean_input, is_blank, repeated_line, Borders, Row},
+    OverflowPolicy, Renderer, RowKind, StyledLine, TextBoxConfig,
+};
+
+/// A box that is written row by row as the lines are supplied.
+///
+/// Created by [`Renderer::stream`] or [`BoxStream::open`]. The top border is written when
+/// the stream is opened, each line is written as soon as it's supplied, and the bottom border
+/// is written by [`finish`](BoxStream::finish) or [`close`](BoxStream::close), so the memory
+/// usage doesn't depend on the number of lines. A stream dropped before being closed writes
+/// the bottom border as well, ignoring the errors. The lines are truncated rather than
+/// [expanded](OverflowPolicy::Expand), since the rows already written can't be widened.
+#[derive(Debug)]
+pub struct BoxStream<'r, 'a, W: io::Write> {
+    renderer: Cow<'r, Renderer<'a>>,
+    /// The writer, taken out when the stream is finished.
+    writer: Option<W>,
+    /// Whether the end of the box has been written, successfully or not.
+    closed: bool,
+    /// The number of rows written so far, borders included.
+    written: usize,
+    /// The number of content rows written so far.
+    rows: usize,
+    /// The number of input lines written so far.
+    lines: usize,
+    /// The number of blank lines held back to be collapsed or trimmed.
+    blank_lines: usize,
+    /// The last line and the number of its repeats, held back to be deduplicated.
+    repeated: Option<(String, usize)>,
+    /// The remaining characters of the side caption.
+    side_caption: Chars<'a>,
+}
+
+impl<'r, 'a, W: io::Write> BoxStream<'r, 'a, W> {
+    /// Write the top border of the box and the timestamp row, if any.
+    pub(crate) fn new(renderer: Cow<'r, Renderer<'a>>, writer: W) -> io::Result<Self> {
+        if renderer.fixed_borders().is_none() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "streaming requires a renderer with a fixed width",
+            ));
+        }
+
+        // The rows that are already written can't be widened.
+        let renderer = match (renderer.config().overflow, renderer.width()) {
+            (OverflowPolicy::Expand, Some(width)) => {
+                let config = renderer.config().with_overflow(OverflowPolicy::Truncate);
+                Cow::Owned(Renderer::with_width(config, width))
+            }
+            _ => renderer,
+        };
+
+        let config = *renderer.config();
+        let mut stream = Self {
+            renderer,
+            writer: Some(writer),
+            closed: false,
+            written: 0,
+            rows: 0,
+            lines: 0,
+            blank_lines: 0,
+            repeated: None,
+            side_caption: config.side_caption.unwrap_or_default().chars(),
+        };
+        let top = stream.borders().top.clone();
+        stream.emit(RowKind::Border, top)?;
+        stream.write_padding()?;
+
+        if let Some(timestamp) = config.timestamp.map(|f| f()) {
+            let alignment = stream.renderer.default_alignment().opposite();
+            let row = stream.renderer.content_row(timestamp.into(), alignment);
+            stream.write_content(row)?;
+        }
+
+        Ok(stream)
+    }
+
+    /// Open a box of the given width on the writer, owning its renderer,
+    /// so the stream can be kept around while the lines come in.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let config = TextBoxConfig::default().with_caption("Events");
+    /// let mut stream = BoxStream::open(Vec::new(), 14, config)?;
+    /// stream.write_line("connected")?;
+    /// stream.write_separator()?;
+    /// stream.write_line("closed")?;
+    /// let output = stream.finish()?;
+    ///
+    /// assert_eq!(String::from_utf8(output).unwrap(), "\
+    /// ╭────────────╮
+    /// │ connected  │
+    /// ├────────────┤
+    /// │ closed     │
+    /// <Events>─────╯");
+    /// # std::io::Result::Ok(())
+    /// ```
+    pub fn open(writer: W, width: usize, config: TextBoxConfig<'a>) -> io::Result<Self> {
+        Self::new(Cow::Owned(Renderer::with_width(config, width)), writer)
+    }
+
+    /// Write the given line as the next row of the box.
+    /// With [`collapse_blank_lines`](crate::TextBoxConfig::collapse_blank_lines) or
+    /// [`trim_blank_lines`](crate::TextBoxConfig::trim_blank_lines), the blank lines
+    /// are held back until the next line with visible characters. With
+    /// [`dedupe_lines`](crate::TextBoxConfig::dedupe_lines), the line is held back
+    /// until a different one is supplied. A line with embedded line endings is written
+    /// as several rows.
+    pub fn write_line(&mut self, line: &str) -> io::Result<()> {
+        if line.contains('\n') {
+            return line.lines().try_for_each(|line| self.write_line(line));
+        }
+        if let Cow::Owned(line) = clean_input(self.renderer.config(), line) {
+            return self.write_line(&line);
+        }
+        let config = self.renderer.config();
+        if config.dedupe_lines && !is_blank(line) {
+            if let Some((previous, count)) = &mut self.repeated {
+                if previous == line {
+                    *count += 1;
+                    return Ok(());
+                }
+            }
+            self.write_repeated()?;
+            self.repeated = Some((line.to_string(), 1));
+            return Ok(());
+        }
+        self.write_repeated()?;
+        self.write_text(line)
+    }
+
+    /// Write the line held back by [`write_line`](Self::write_line) to be deduplicated.
+    fn write_repeated(&mut self) -> io::Result<()> {
+        match self.repeated.take() {
+            Some((line, count)) => {
+                let line = repeated_line(self.renderer.config(), &line, count);
+                self.write_text(&line)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Write the line as the next row of the box, holding back the blank lines.
+    fn write_text(&mut self, line: &str) -> io::Result<()> {
+        let config = self.renderer.config();
+        if (config.collapse_blank_lines || config.trim_blank_lines) && is_blank(line) {
+            self.blank_lines += 1;
+            return Ok(());
+        }
+        self.write_blank_lines()?;
+
+        let rows = self.renderer.line_rows(self.lines, line);
+        self.write_line_rows(rows)
+    }
+
+    /// Write the given rich text line as the next row of the box.
+    /// See [`Renderer::render_styled`].
+    pub fn write_styled(&mut self, line: &StyledLine<'_>) -> io::Result<()> {
+        self.write_repeated()?;
+        self.write_blank_lines()?;
+        let rows = self.renderer.styled_rows(self.lines, line);
+        self.write_line_rows(rows)
+    }
+
+    /// Write a horizontal rule separating the lines written so far from the next ones.
+    pub fn write_separator(&mut self) -> io::Result<()> {
+        self.write_repeated()?;
+        self.write_blank_lines()?;
+        self.write_row(&Row::Rule)
+    }
+
+    /// Write the footer and the bottom border, returning the underlying writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.write_end()?;
+        Ok(self.writer.take().expect("the stream is open"))
+    }
+
+    /// Write the footer and the bottom border, then drop the underlying writer.
+    pub fn close(self) -> io::Result<()> {
+        self.finish().map(drop)
+    }
+
+    /// Write the end of the box, once.
+    fn write_end(&mut self) -> io::Result<()> {
+        if self.closed {
+            return Ok(());
+        }
+        self.closed = true;
+        self.write_repeated()?;
+
+        let config = *self.renderer.config();
+        if !config.trim_blank_lines {
+            self.write_blank_lines()?;
+        }
+        let footer = self.renderer.footer();
+
+        let content_rows = self.rows + footer.is_some() as usize + config.vertical_padding;
+        let side_caption_rows = config.side_caption.map_or(0, |c| c.chars().count());
+        let alignment = self.renderer.default_alignment();
+        for _ in content_rows..side_caption_rows {
+            self.write_content(Row::blank(alignment))?;
+        }
+
+        if let Some(footer) = footer {
+            self.write_row(&Row::Rule)?;
+            let row = self.renderer.content_row(footer.into(), alignment);
+            self.write_content(row)?;
+        }
+        self.write_padding()?;
+
+        let bottom = self.borders().bottom.clone();
+        self.emit(self.renderer.bottom_kind(), bottom)?;
+
+        if let Some(column) = config.tail_column {
+            let tail = format!("{:column$}{}", "", config.tail, column = column);
+            self.emit(RowKind::Tail, tail)?;
+        }
+        if config.trailing_newline {
+            let writer = self.writer.as_mut().expect("the stream is open");
+            writer.write_all(config.line_ending.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Write the blank rows of the vertical padding.
+    fn write_padding(&mut self) -> io::Result<()> {
+        let alignment = self.renderer.default_alignment();
+        for _ in 0..self.renderer.config().vertical_padding {
+            self.write_content(Row::blank(alignment))?;
+        }
+        Ok(())
+    }
+
+    /// The borders of the fixed-width renderer, checked when the stream is opened.
+    fn borders(&self) -> &Borders {
+        self.renderer.fixed_borders().expect("the width is fixed")
+    }
+
+    /// Write the blank lines held back by [`write_line`](Self::write_line).
+    fn write_blank_lines(&mut self) -> io::Result<()> {
+        let config = self.renderer.config();
+        let mut count = std::mem::take(&mut self.blank_lines);
+        if config.trim_blank_lines && self.lines == 0 {
+            count = 0;
+        }
+        if config.collapse_blank_lines {
+            count = count.min(1);
+        }
+
+        for _ in 0..count {
+            let rows = self.renderer.line_rows(self.lines, "");
+            self.write_line_rows(rows)?;
+        }
+        Ok(())
+    }
+
+    /// Write the rows of the next input line, preceded by the blank rows of the line spacing.
+    fn write_line_rows(&mut self, rows: Vec<Row<'_>>) -> io::Result<()> {
+        if self.lines > 0 {
+            let alignment = self.renderer.default_alignment();
+            for _ in 0..self.renderer.config().line_spacing {
+                self.write_content(Row::blank(alignment))?;
+            }
+        }
+        self.lines += 1;
+        rows.into_iter().try_for_each(|row| self.write_content(row))
+    }
+
+    fn write_content(&mut self, mut row: Row<'_>) -> io::Result<()> {
+        if let Row::Content { border_glyph, .. } = &mut row {
+            *border_glyph = self.side_caption.next();
+        }
+        self.rows += 1;
+        self.write_row(&row)
+    }
+
+    fn write_row(&mut self, row: &Row<'_>) -> io::Result<()> {
+        let rendered = self.borders().render_row(row, self.renderer.config());
+        self.emit(row.kind(), rendered)
+    }
+
+    /// Pass the row through the post-render hook and write it on a separate line.
+    fn emit(&mut self, kind: RowKind, row: String) -> io::Result<()> {
+        let row = self.renderer.finish_row(self.written, kind, row);
+        let line_ending = self.renderer.config().line_ending;
+        let writer = self.writer.as_mut().expect("the stream is open");
+        if self.written > 0 {
+            writer.write_all(line_ending.as_bytes())?;
+        }
+        self.written += 1;
+        writer.write_all(row.as_bytes())
+    }
+}
+
+impl<W: io::Write> Drop for BoxStream<'_, '_, W> {
+    fn drop(&mut self) {
+        let _ = self.write_end();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        BoxStream, ControlPolicy, OverflowPolicy, Renderer, RowKind, Side, Style, StyledLine,
+        TextBoxConfig,
+    };
+
+    #[test]
+    fn test_stream_matches_render() {
+        let timestamp = || "ts".to_string();
+        let footer = || vec!["done".to_string()];
+        let config = TextBoxConfig::dos()
+            .with_caption("Stream")
+            .with_timestamp(&timestamp)
+            .with_footer(&footer)
+            .with_side_caption("ABCDEFG", Side::Right)
+            .with_tail(2);
+        let renderer = Renderer::with_width(config, 14);
+        let lines = ["first".to_string(), "a line that is too long".to_string()];
+
+        let mut stream = renderer.stream(Vec::new()).unwrap();
+        for line in &lines {
+            stream.write_line(line).unwrap();
+        }
+        let streamed = String::from_utf8(stream.finish().unwrap()).unwrap();
+
+        assert_eq!(streamed, renderer.render(&lines));
+    }
+
+    #[test]
+    fn test_stream_post_render() {
+        let hook =
+            |index: usize, kind: RowKind, row: String| format!("{} {:?} {}", index, kind, row);
+        let footer = || vec![];
+        let config = TextBoxConfig::default()
+            .with_post_render(&hook)
+            .with_footer(&footer)
+            .with_caption("C")
+            .with_tail(1);
+        let renderer = Renderer::with_width(config, 5);
+
+        let mut stream = renderer.stream(Vec::new()).unwrap();
+        stream.write_line("abc").unwrap();
+        let streamed = String::from_utf8(stream.finish().unwrap()).unwrap();
+
+        assert_eq!(streamed, renderer.render(&["abc".to_string()]));
+    }
+
+    #[test]
+    fn test_stream_blank_lines() {
+        let lines = ["", " ", "a", "", "", "b", "b", "", "c", "c", "c", "", ""]
+            .iter()
+            .map(|line| line.to_string())
+            .collect::<Vec<_>>();
+        let configs = [
+            TextBoxConfig::default().with_line_spacing(2),
+            TextBoxConfig::default()
+                .collapse_blank_lines()
+                .with_line_spacing(1),
+            TextBoxConfig::default().collapse_blank_lines(),
+            TextBoxConfig::default().trim_blank_lines(),
+            TextBoxConfig::default()
+                .collapse_blank_lines()
+                .trim_blank_lines(),
+            TextBoxConfig::default().dedupe_lines(),
+            TextBoxConfig::default()
+                .dedupe_lines()
+                .collapse_blank_lines()
+                .trim_blank_lines(),
+        ];
+
+        for config in configs.iter() {
+            let renderer = Renderer::with_width(*config, 5);
+            let mut stream = renderer.stream(Vec::new()).unwrap();
+            for line in &lines {
+                stream.write_line(line).unwrap();
+            }
+            let streamed = String::from_utf8(stream.finish().unwrap()).unwrap();
+            assert_eq!(streamed, renderer.render(&lines));
+        }
+    }
+
+    #[test]
+    fn test_stream_requires_fixed_width() {
+        let renderer = Renderer::new(TextBoxConfig::default());
+        let error = renderer.stream(Vec::new()).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_stream_wraps() {
+        let config = TextBoxConfig::default()
+            .with_overflow(OverflowPolicy::Wrap)
+            .with_line_spacing(1)
+            .with_markup();
+        let renderer = Renderer::with_width(config, 12);
+        let lines = [
+            "the quick brown fox".to_string(),
+            "**jumps over** the dog".to_string(),
+        ];
+
+        let mut stream = renderer.stream(Vec::new()).unwrap();
+        for line in &lines {
+            stream.write_line(line).unwrap();
+        }
+        let streamed = String::from_utf8(stream.finish().unwrap()).unwrap();
+
+        assert_eq!(streamed, renderer.render(&lines));
+    }
+
+    #[test]
+    fn test_open_close() {
+        let config = TextBoxConfig::default().with_caption("Log");
+        let renderer = Renderer::with_width(config, 10);
+        let expected = renderer.render_separated(&[&["a".to_string()], &["b".to_string()]]);
+
+        let mut output = Vec::new();
+        let mut stream = BoxStream::open(&mut output, 10, config).unwrap();
+        stream.write_line("a").unwrap();
+        stream.write_separator().unwrap();
+        stream.write_line("b").unwrap();
+        stream.close().unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), expected);
+
+        let mut output = Vec::new();
+        {
+            let mut stream = BoxStream::open(&mut output, 10, config).unwrap();
+            stream.write_line("a").unwrap();
+        }
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            renderer.render(&["a".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_expand_is_truncated() {
+        let config = TextBoxConfig::ascii().with_overflow(OverflowPolicy::Expand);
+        let mut out = Vec::new();
+        let mut stream = BoxStream::open(&mut out, 8, config).unwrap();
+        stream.write_line("too long").unwrap();
+        stream.close().unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "+------+\n| too  |\n+------+"
+        );
+    }
+
+    #[test]
+    fn test_vertical_padding() {
+        let config = TextBoxConfig::ascii().with_vertical_padding(1);
+        let mut out = Vec::new();
+        let mut stream = BoxStream::open(&mut out, 6, config).unwrap();
+        stream.write_line("ok").unwrap();
+        stream.close().unwrap();
+        let lines = [String::from("ok")];
+        let expected = Renderer::with_width(config, 6).render(&lines);
+        assert_eq!(String::from_utf8(out).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_embedded_newlines() {
+        let config = TextBoxConfig::ascii()
+            .with_tab_width(2)
+            .with_control_chars(ControlPolicy::Strip);
+        let mut out = Vec::new();
+        let mut stream = BoxStream::open(&mut out, 8, config).unwrap();
+        stream.write_line("a\r\nb\tc\0\n").unwrap();
+        stream.close().unwrap();
+        let lines = [String::from("a"), String::from("b c")];
+        let expected = Renderer::with_width(config, 8).render(&lines);
+        assert_eq!(String::from_utf8(out).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_line_ending() {
+        let config = TextBoxConfig::ascii()
+            .with_line_ending("\r\n")
+            .trailing_newline()
+            .with_vertical_padding(1)
+            .with_caption("c");
+        let mut out = Vec::new();
+        let mut stream = BoxStream::open(&mut out, 6, config).unwrap();
+        stream.write_line("ok").unwrap();
+        stream.close().unwrap();
+        let lines = [String::from("ok")];
+        let expected = Renderer::with_width(config, 6).render(&lines);
+        assert_eq!(String::from_utf8(out).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_styled_after_blank_lines() {
+        let config = TextBoxConfig::ascii().collapse_blank_lines().strip_styles();
+        let mut out = Vec::new();
+        let mut stream = BoxStream::open(&mut out, 5, config).unwrap();
+        stream.write_line("a").unwrap();
+        stream.write_line("").unwrap();
+        stream.write_line("").unwrap();
+        let styled = StyledLine::new().with_span("b", Style::new().bold());
+        stream.write_styled(&styled).unwrap();
+        stream.close().unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "+---+\n| a |\n|   |\n| b |\n+---+"
+        );
+    }
+}
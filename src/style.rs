@@ -0,0 +1,483 @@
+//! Terminal styles applied to the content of the boxes.
+use std::{borrow::Cow, fmt, ops::Range};
+
+use crate::{markup, Alignment};
+
+/// The escape sequence resetting all of the terminal styles.
+pub const RESET: &str = "\x1b[0m";
+
+/// A terminal color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    /// The black color.
+    Black,
+    /// The red color.
+    Red,
+    /// The green color.
+    Green,
+    /// The yellow color.
+    Yellow,
+    /// The blue color.
+    Blue,
+    /// The magenta color.
+    Magenta,
+    /// The cyan color.
+    Cyan,
+    /// The white color.
+    White,
+    /// A color from the 256-color palette.
+    Fixed(u8),
+    /// A 24-bit color.
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    /// Find the basic color with the given lowercase name, like `red`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let color = match name {
+            "black" => Color::Black,
+            "red" => Color::Red,
+            "green" => Color::Green,
+            "yellow" => Color::Yellow,
+            "blue" => Color::Blue,
+            "magenta" => Color::Magenta,
+            "cyan" => Color::Cyan,
+            "white" => Color::White,
+            _ => return None,
+        };
+        Some(color)
+    }
+
+    /// Write the SGR parameters selecting this color, using `base` (30 for foreground,
+    /// 40 for background) as the offset of the basic colors.
+    fn write_params(self, f: &mut impl fmt::Write, base: u8) -> fmt::Result {
+        match self {
+            Color::Black => write!(f, "{}", base),
+            Color::Red => write!(f, "{}", base + 1),
+            Color::Green => write!(f, "{}", base + 2),
+            Color::Yellow => write!(f, "{}", base + 3),
+            Color::Blue => write!(f, "{}", base + 4),
+            Color::Magenta => write!(f, "{}", base + 5),
+            Color::Cyan => write!(f, "{}", base + 6),
+            Color::White => write!(f, "{}", base + 7),
+            Color::Fixed(n) => write!(f, "{};5;{}", base + 8, n),
+            Color::Rgb(r, g, b) => write!(f, "{};2;{};{};{}", base + 8, r, g, b),
+        }
+    }
+}
+
+/// A combination of terminal colors and text attributes, emitted as ANSI escape sequences.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// let style = Style::new().fg(Color::Red).bold();
+/// assert_eq!(style.paint("error"), "\x1b[1;31merror\x1b[0m");
+/// assert_eq!(Style::new().paint("plain"), "plain");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Style {
+    /// The foreground color.
+    pub fg: Option<Color>,
+    /// The background color.
+    pub bg: Option<Color>,
+    /// Whether the text is bold.
+    pub bold: bool,
+    /// Whether the text is dimmed.
+    pub dim: bool,
+    /// Whether the text is italic.
+    pub italic: bool,
+    /// Whether the text is underlined.
+    pub underline: bool,
+}
+
+impl Style {
+    /// Create a style that doesn't change the appearance of the text.
+    pub const fn new() -> Self {
+        Self {
+            fg: None,
+            bg: None,
+            bold: false,
+            dim: false,
+            italic: false,
+            underline: false,
+        }
+    }
+
+    /// Set the foreground color.
+    pub const fn fg(self, color: Color) -> Self {
+        Self {
+            fg: Some(color),
+            ..self
+        }
+    }
+
+    /// Set the background color.
+    pub const fn bg(self, color: Color) -> Self {
+        Self {
+            bg: Some(color),
+            ..self
+        }
+    }
+
+    /// Make the text bold.
+    pub const fn bold(self) -> Self {
+        Self { bold: true, ..self }
+    }
+
+    /// Make the text dimmed.
+    pub const fn dim(self) -> Self {
+        Self { dim: true, ..self }
+    }
+
+    /// Make the text italic.
+    pub const fn italic(self) -> Self {
+        Self {
+            italic: true,
+            ..self
+        }
+    }
+
+    /// Make the text underlined.
+    pub const fn underline(self) -> Self {
+        Self {
+            underline: true,
+            ..self
+        }
+    }
+
+    /// Whether the style leaves the text as is.
+    pub fn is_plain(&self) -> bool {
+        *self == Self::new()
+    }
+
+    /// The escape sequence enabling this style, empty if the style is plain.
+    pub fn start(&self) -> String {
+        let mut start = String::new();
+        if self.is_plain() {
+            return start;
+        }
+
+        let mut params = Vec::with_capacity(6);
+        let flags = [
+            (self.bold, "1"),
+            (self.dim, "2"),
+            (self.italic, "3"),
+            (self.underline, "4"),
+        ];
+        params.extend(
+            flags
+                .iter()
+                .filter(|(on, _)| *on)
+                .map(|(_, p)| p.to_string()),
+        );
+        for (color, base) in [(self.fg, 30), (self.bg, 40)].iter() {
+            if let Some(color) = color {
+                let mut param = String::new();
+                color
+                    .write_params(&mut param, *base)
+                    .expect("writing to a string never fails");
+                params.push(param);
+            }
+        }
+
+        start.push_str("\x1b[");
+        start.push_str(&params.join(";"));
+        start.push('m');
+        start
+    }
+
+    /// Surround the given text with the escape sequences enabling and resetting this style.
+    pub fn paint<'t>(&self, text: impl Into<Cow<'t, str>>) -> Cow<'t, str> {
+        let text = text.into();
+        if self.is_plain() {
+            return text;
+        }
+        Cow::Owned(self.start() + &text + RESET)
+    }
+}
+
+/// The way a single content line is displayed, as returned by [`TextBoxConfig::line_style`](crate::TextBoxConfig::line_style).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LineStyle {
+    /// Overrides the alignment of the line within the box.
+    pub alignment: Option<Alignment>,
+    /// Displayed before the line and counted towards its width.
+    pub prefix: Cow<'static, str>,
+    /// The style of the prefix and the line. Padding is never styled.
+    pub style: Style,
+}
+
+impl LineStyle {
+    /// Create a line style that displays the line as is.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Align the line in the given way.
+    pub fn with_alignment(self, alignment: Alignment) -> Self {
+        Self {
+            alignment: Some(alignment),
+            ..self
+        }
+    }
+
+    /// Display the given prefix before the line.
+    pub fn with_prefix(self, prefix: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            prefix: prefix.into(),
+            ..self
+        }
+    }
+
+    /// Display the line in the given terminal style.
+    pub fn with_style(self, style: Style) -> Self {
+        Self { style, ..self }
+    }
+}
+
+/// A piece of text displayed in a single style.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Span<'t> {
+    /// The visible text of the span.
+    pub text: Cow<'t, str>,
+    /// The style of the text.
+    pub style: Style,
+}
+
+impl<'t> Span<'t> {
+    /// Create a span displaying the text in the given style.
+    pub fn new(text: impl Into<Cow<'t, str>>, style: Style) -> Self {
+        Self {
+            text: text.into(),
+            style,
+        }
+    }
+}
+
+/// A line of rich text made of styled spans, rendered by [`Renderer::render_styled`](crate::Renderer::render_styled).
+///
+/// ```
+/// # use plain_msgbox::*;
+/// let line = StyledLine::new()
+///     .with_span("disk: ", Style::new().bold())
+///     .with_span("94%", Style::new().fg(Color::Red));
+///
+/// assert_eq!(line.text(), "disk: 94%");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StyledLine<'t> {
+    /// The spans of the line, from left to right.
+    pub spans: Vec<Span<'t>>,
+}
+
+impl<'t> StyledLine<'t> {
+    /// Create an empty line.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a span with the given text and style.
+    pub fn with_span(mut self, text: impl Into<Cow<'t, str>>, style: Style) -> Self {
+        self.push(text, style);
+        self
+    }
+
+    /// Append a span with the given text and style in place.
+    pub fn push(&mut self, text: impl Into<Cow<'t, str>>, style: Style) {
+        self.spans.push(Span::new(text, style));
+    }
+
+    /// Parse a line written in the inline markup:
+    /// `**bold**`, `_dim_`, and `[red]colored[/red]` text, with `\\` escaping the next character.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let line = StyledLine::from_markup("**disk**: [red]94%[/red]");
+    ///
+    /// assert_eq!(line.text(), "disk: 94%");
+    /// assert_eq!(line.spans[0].style, Style::new().bold());
+    /// assert_eq!(line.spans[2].style, Style::new().fg(Color::Red));
+    /// ```
+    pub fn from_markup(markup: &str) -> StyledLine<'static> {
+        markup::parse(markup)
+    }
+
+    /// Split the text colored with the ANSI escape sequences, like the ones emitted by
+    /// [`Style::paint`], into the styled spans. The unsupported sequences are ignored.
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let line = StyledLine::from_ansi("disk: \x1b[1;31m94%\x1b[0m");
+    ///
+    /// assert_eq!(line, StyledLine::new()
+    ///     .with_span("disk: ", Style::new())
+    ///     .with_span("94%", Style::new().fg(Color::Red).bold()));
+    /// ```
+    pub fn from_ansi(text: &'t str) -> Self {
+        parse_sgr(text)
+    }
+
+    /// Split the displayed value colored with the ANSI escape sequences, like the values
+    /// styled by the `owo-colors` or `colored` crates, into the styled spans.
+    /// See [`from_ansi`](Self::from_ansi).
+    ///
+    /// ```
+    /// # use plain_msgbox::*;
+    /// let line = StyledLine::from_display(format_args!("{}ok", Style::new().fg(Color::Green).start()));
+    ///
+    /// assert_eq!(line, StyledLine::new().with_span("ok", Style::new().fg(Color::Green)));
+    /// ```
+    pub fn from_display(value: impl fmt::Display) -> StyledLine<'static> {
+        StyledLine::from_ansi(&value.to_string()).into_owned()
+    }
+
+    /// Copy the borrowed text of the spans, so the line doesn't borrow anything.
+    pub fn into_owned(self) -> StyledLine<'static> {
+        let spans = self
+            .spans
+            .into_iter()
+            .map(|span| Span::new(span.text.into_owned(), span.style))
+            .collect();
+        StyledLine { spans }
+    }
+
+    /// The part of the line within the given byte range of its visible text.
+    pub(crate) fn slice(&self, range: Range<usize>) -> StyledLine<'_> {
+        let mut line = StyledLine::new();
+        let mut start = 0;
+        for span in &self.spans {
+            let end = start + span.text.len();
+            let (from, to) = (range.start.max(start), range.end.min(end));
+            if from < to {
+                line.push(&span.text[from - start..to - start], span.style);
+            }
+            start = end;
+        }
+        line
+    }
+
+    /// The visible text of the line, without the escape sequences.
+    pub fn text(&self) -> String {
+        self.spans.iter().map(|span| &*span.text).collect()
+    }
+}
+
+impl<'t> From<&'t str> for StyledLine<'t> {
+    fn from(text: &'t str) -> Self {
+        Self::new().with_span(text, Style::new())
+    }
+}
+
+impl From<String> for StyledLine<'_> {
+    fn from(text: String) -> Self {
+        Self::new().with_span(text, Style::new())
+    }
+}
+
+/// Split the text into the spans styled by the SGR escape sequences before them.
+fn parse_sgr(text: &str) -> StyledLine<'_> {
+    let mut line = StyledLine::new();
+    let mut style = Style::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("\x1b[") {
+        let end = match rest[start..].find('m') {
+            Some(end) => start + end,
+            None => break,
+        };
+        if start > 0 {
+            line.push(&rest[..start], style);
+        }
+        style = apply_sgr(style, &rest[start + 2..end]);
+        rest = &rest[end + 1..];
+    }
+    if !rest.is_empty() {
+        line.push(rest, style);
+    }
+    line
+}
+
+/// Apply the given SGR parameters, as written by [`Style::start`], to the style.
+pub(crate) fn apply_sgr(mut style: Style, params: &str) -> Style {
+    let mut params = params
+        .split(';')
+        .map(|param| param.parse::<u8>().unwrap_or(0));
+    while let Some(param) = params.next() {
+        let mut extended = || match params.next() {
+            Some(5) => params.next().map(Color::Fixed),
+            Some(2) => {
+                let mut channel = || params.next().unwrap_or(0);
+                Some(Color::Rgb(channel(), channel(), channel()))
+            }
+            _ => None,
+        };
+        match param {
+            0 => style = Style::new(),
+            1 => style.bold = true,
+            2 => style.dim = true,
+            3 => style.italic = true,
+            4 => style.underline = true,
+            30..=37 => style.fg = Some(BASIC_COLORS[(param - 30) as usize]),
+            38 => style.fg = extended(),
+            40..=47 => style.bg = Some(BASIC_COLORS[(param - 40) as usize]),
+            48 => style.bg = extended(),
+            90..=97 => style.fg = Some(Color::Fixed(param - 90 + 8)),
+            100..=107 => style.bg = Some(Color::Fixed(param - 100 + 8)),
+            _ => {}
+        }
+    }
+    style
+}
+
+/// The basic colors in the order of their SGR parameters.
+const BASIC_COLORS: [Color; 8] = [
+    Color::Black,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::White,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_style_sequences() {
+        assert_eq!(Style::new().start(), "");
+        assert_eq!(Style::new().fg(Color::Green).start(), "\x1b[32m");
+        assert_eq!(
+            Style::new()
+                .bg(Color::Fixed(208))
+                .fg(Color::Rgb(1, 2, 3))
+                .underline()
+                .dim()
+                .start(),
+            "\x1b[2;4;38;2;1;2;3;48;5;208m"
+        );
+        assert_eq!(
+            Style::new().italic().bg(Color::White).paint("x"),
+            "\x1b[3;47mx\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_from_ansi() {
+        let styles = [
+            Style::new().fg(Color::White).bold(),
+            Style::new().bg(Color::Fixed(208)).italic().underline(),
+            Style::new().fg(Color::Rgb(1, 2, 3)).bg(Color::Blue).dim(),
+        ];
+        for style in styles.iter() {
+            let row = format!("a{}b", style.paint("c"));
+            let plain = Style::new();
+            let expected = StyledLine::new()
+                .with_span("a", plain)
+                .with_span("c", *style)
+                .with_span("b", plain);
+            assert_eq!(StyledLine::from_ansi(&row), expected);
+        }
+    }
+}
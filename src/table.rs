@@ -0,0 +1,382 @@
+//! Tables with the columns sized to their cells, see [`generate_table`].
+use crate::{
+    charset::glyph,
+    default, dos, generate_with_config,
+    measure::{self, Measure},
+    renderer::{caption_alignment, glyph_width, is_numeric, BORDER_WIDTH},
+    spacer::push_spaces,
+    Alignment, TextBoxConfig,
+};
+
+/// The ASCII fallback of the junction glyphs.
+const ASCII_JUNCTION: &str = "+";
+
+/// The config of a table: the box characters of the border and the junctions of the border
+/// with the column dividers.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// let config = TableConfig::new(TextBoxConfig::dos()).with_header();
+/// assert_eq!((config.top_junction, config.cross), ("╤", "╪"));
+/// assert!(config.header);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct TableConfig<'a> {
+    /// The config of the box. The [`Border`](crate::Border) of the config isn't supported,
+    /// and the [`column_divider`](TextBoxConfig::column_divider) separates the cells.
+    pub config: TextBoxConfig<'a>,
+    /// Whether the first row is the header, separated from the other rows by a rule.
+    pub header: bool,
+    /// The junction of the top border with the column dividers.
+    pub top_junction: &'a str,
+    /// The junction of the bottom border with the column dividers.
+    pub bottom_junction: &'a str,
+    /// The junction of the header rule with the column dividers.
+    pub cross: &'a str,
+}
+
+impl<'a> TableConfig<'a> {
+    /// Create a table config with the junctions matching the horizontal bar of the given
+    /// config: the double ones for the DOS bars, and the single ones otherwise.
+    pub fn new(config: TextBoxConfig<'a>) -> Self {
+        let (top_junction, bottom_junction, cross) =
+            if config.horizontal_bar == dos::DOS_HORIZONTAL_BAR {
+                (
+                    dos::DOS_TOP_JUNCTION,
+                    dos::DOS_BOTTOM_JUNCTION,
+                    dos::DOS_CROSS,
+                )
+            } else {
+                (
+                    default::DEFAULT_TOP_JUNCTION,
+                    default::DEFAULT_BOTTOM_JUNCTION,
+                    default::DEFAULT_CROSS,
+                )
+            };
+        Self {
+            config,
+            header: false,
+            top_junction,
+            bottom_junction,
+            cross,
+        }
+    }
+
+    /// Separate the first row from the other rows by a rule.
+    pub fn with_header(self) -> Self {
+        Self {
+            header: true,
+            ..self
+        }
+    }
+
+    /// Use the given junctions of the top border, the bottom border and the header rule
+    /// with the column dividers.
+    pub fn with_junctions(self, top: &'a str, bottom: &'a str, cross: &'a str) -> Self {
+        Self {
+            top_junction: top,
+            bottom_junction: bottom,
+            cross,
+            ..self
+        }
+    }
+}
+
+impl Default for TableConfig<'_> {
+    fn default() -> Self {
+        Self::new(TextBoxConfig::default())
+    }
+}
+
+/// Generate a table with the given rows of cells. Every column is as wide as its widest cell,
+/// and the rows with fewer cells are padded with the empty ones. With a
+/// [fixed](TextBoxConfig::with_width) or a [maximum](TextBoxConfig::with_max_width) width,
+/// the widest columns are narrowed first and their cells are truncated.
+/// The junctions fall back to ASCII like the other glyphs of the config.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// let rows = [
+///     vec![String::from("Crate"), String::from("Version")],
+///     vec![String::from("serde"), String::from("1.0.200")],
+///     vec![String::from("rayon"), String::from("1.10")],
+/// ];
+/// let config = TableConfig::new(TextBoxConfig::default().with_caption("deps")).with_header();
+///
+/// assert_eq!(generate_table(&rows, config), "\
+/// ╭───────┬─────────╮
+/// │ Crate │ Version │
+/// ├───────┼─────────┤
+/// │ serde │ 1.0.200 │
+/// │ rayon │ 1.10    │
+/// <deps>──┴─────────╯");
+/// ```
+pub fn generate_table(rows: &[Vec<String>], config: TableConfig<'_>) -> String {
+    let box_config = config.config.charset.apply(config.config);
+    let config = TableConfig {
+        config: box_config,
+        top_junction: glyph(&box_config, [config.top_junction, ASCII_JUNCTION]),
+        bottom_junction: glyph(&box_config, [config.bottom_junction, ASCII_JUNCTION]),
+        cross: glyph(&box_config, [config.cross, ASCII_JUNCTION]),
+        ..config
+    };
+    let measure = box_config.measure();
+
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    if columns == 0 {
+        return generate_with_config(&[], box_config);
+    }
+    let mut widths = vec![0; columns];
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(measure.width(cell));
+        }
+    }
+    fit_columns(&config, measure, &mut widths);
+
+    let mut out = String::new();
+    push_rule(
+        &mut out,
+        &box_config,
+        &widths,
+        Rule::Top(config.top_junction),
+    );
+    for (index, row) in rows.iter().enumerate() {
+        out.push_str(box_config.line_ending);
+        push_cells(&mut out, &box_config, measure, &widths, row);
+        if config.header && index == 0 && rows.len() > 1 {
+            out.push_str(box_config.line_ending);
+            push_rule(&mut out, &box_config, &widths, Rule::Header(config.cross));
+        }
+    }
+    out.push_str(box_config.line_ending);
+    push_rule(
+        &mut out,
+        &box_config,
+        &widths,
+        Rule::Bottom(config.bottom_junction),
+    );
+    if box_config.trailing_newline {
+        out.push_str(box_config.line_ending);
+    }
+    out
+}
+
+/// A horizontal rule of a table, with the junction of the rule with the column dividers.
+#[derive(Debug, Clone, Copy)]
+enum Rule<'g> {
+    Top(&'g str),
+    Header(&'g str),
+    Bottom(&'g str),
+}
+
+/// The width of the table with the columns of the given widths, borders included.
+fn table_width(config: &TextBoxConfig<'_>, widths: &[usize]) -> usize {
+    let dividers = (widths.len() - 1) * (glyph_width(config, config.column_divider) + 2);
+    widths.iter().sum::<usize>() + dividers + BORDER_WIDTH
+}
+
+/// Widen the last column to fit the caption or to reach the fixed or the minimum width,
+/// and narrow the widest columns to fit into the fixed or the maximum width.
+fn fit_columns(config: &TableConfig<'_>, measure: Measure<'_>, widths: &mut [usize]) {
+    let box_config = &config.config;
+    let caption = box_config.last_line_caption.map_or(0, |c| measure.width(c));
+    let width = table_width(box_config, widths);
+    let limit = box_config.width.or(box_config.max_width);
+    let target = match box_config.width {
+        Some(fixed) => fixed,
+        None => width
+            .max(caption + BORDER_WIDTH)
+            .max(box_config.min_width.unwrap_or(0)),
+    };
+    let target = limit.map_or(target, |limit| target.min(limit));
+
+    let last = widths.len() - 1;
+    if target > width {
+        widths[last] += target - width;
+    }
+    for _ in target..width {
+        let widest = (0..widths.len()).rev().max_by_key(|&column| widths[column]);
+        match widest {
+            Some(column) if widths[column] > 0 => widths[column] -= 1,
+            _ => break,
+        }
+    }
+}
+
+/// Append a horizontal rule spanning the columns of the given widths.
+/// The caption of the config is embedded into the bottom border at its
+/// [alignment](TextBoxConfig::caption_alignment), covering the junctions under it.
+fn push_rule(out: &mut String, config: &TextBoxConfig<'_>, widths: &[usize], rule: Rule<'_>) {
+    let (left, right, junction) = match rule {
+        Rule::Top(junction) => (config.left_top_corner, config.right_top_corner, junction),
+        Rule::Header(junction) => (config.left_tee, config.right_tee, junction),
+        Rule::Bottom(junction) => (
+            config.left_bottom_corner,
+            config.right_bottom_corner,
+            junction,
+        ),
+    };
+    let divider_width = glyph_width(config, config.column_divider);
+
+    let mut glyphs = vec![left];
+    for (index, &width) in widths.iter().enumerate() {
+        if index > 0 && divider_width > 0 {
+            let before = (divider_width - 1) / 2;
+            glyphs.extend((0..before).map(|_| config.horizontal_bar));
+            glyphs.push(junction);
+            glyphs.extend((before + 1..divider_width).map(|_| config.horizontal_bar));
+        }
+        glyphs.extend((0..width + 2).map(|_| config.horizontal_bar));
+    }
+    glyphs.push(right);
+
+    let push_glyphs =
+        |out: &mut String, glyphs: &[&str]| glyphs.iter().for_each(|g| out.push_str(g));
+    let caption = match config.last_line_caption {
+        Some(caption) if matches!(rule, Rule::Bottom(_)) => caption,
+        _ => return push_glyphs(out, &glyphs),
+    };
+    let measure = config.measure();
+    let caption = measure.truncate(caption, glyphs.len().saturating_sub(BORDER_WIDTH));
+    let covered = measure.width(caption) + 2;
+    let alignment = caption_alignment(config);
+    let start = match alignment {
+        Alignment::Left => 0,
+        Alignment::Center => (glyphs.len() - covered) / 2,
+        Alignment::Right => glyphs.len() - covered,
+    };
+    push_glyphs(out, &glyphs[..start]);
+    out.push('<');
+    out.push_str(caption);
+    out.push('>');
+    push_glyphs(out, &glyphs[start + covered..]);
+}
+
+/// Append a row of cells padded to the widths of their columns.
+fn push_cells(
+    out: &mut String,
+    config: &TextBoxConfig<'_>,
+    measure: Measure<'_>,
+    widths: &[usize],
+    row: &[String],
+) {
+    out.push_str(config.vertical_bar);
+    for (index, &width) in widths.iter().enumerate() {
+        if index > 0 {
+            out.push(' ');
+            out.push_str(config.column_divider);
+        }
+        out.push(' ');
+        let cell = row.get(index).map_or("".into(), |cell| {
+            measure::truncate(&measure, cell.as_str().into(), width)
+        });
+        let padding = width - measure.width(&cell);
+        let alignment = match config.alignment {
+            _ if config.align_numbers && is_numeric(&cell) => Alignment::Right,
+            alignment => alignment,
+        };
+        let before = match alignment {
+            Alignment::Left => 0,
+            Alignment::Center => padding / 2,
+            Alignment::Right => padding,
+        };
+        push_spaces(out, before);
+        out.push_str(&cell);
+        push_spaces(out, padding - before);
+    }
+    out.push(' ');
+    out.push_str(config.vertical_bar);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(rows: &[&[&str]]) -> Vec<Vec<String>> {
+        rows.iter()
+            .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_generate_table() {
+        let rows = rows(&[&["a", "bb"], &["ccc"]]);
+        let config = TableConfig::new(TextBoxConfig::ascii());
+        assert_eq!(
+            generate_table(&rows, config),
+            "\
++-----+----+
+| a   | bb |
+| ccc |    |
++-----+----+"
+        );
+        assert_eq!(
+            generate_table(&rows, config.with_header()),
+            "\
++-----+----+
+| a   | bb |
++-----+----+
+| ccc |    |
++-----+----+"
+        );
+        assert_eq!(generate_table(&[], config), "+--+\n+--+");
+    }
+
+    #[test]
+    fn test_column_dividers() {
+        let rows = rows(&[&["a", "bb"]]);
+        let styled = TextBoxConfig {
+            column_divider: "\x1b[2m|\x1b[0m",
+            ..TextBoxConfig::ascii().with_measurer(&crate::AnsiWidth(crate::CharWidth))
+        };
+        assert_eq!(
+            generate_table(&rows, TableConfig::new(styled)),
+            "+---+----+\n| a \x1b[2m|\x1b[0m bb |\n+---+----+"
+        );
+
+        let empty = TextBoxConfig {
+            column_divider: "",
+            ..TextBoxConfig::ascii()
+        };
+        assert_eq!(
+            generate_table(&rows, TableConfig::new(empty)),
+            "+-------+\n| a  bb |\n+-------+"
+        );
+    }
+
+    #[test]
+    fn test_styled_cells() {
+        let rows = rows(&[&["\x1b[31mabcdef\x1b[0m", "x"]]);
+        let config = TextBoxConfig::ascii().ansi_aware().with_width(10);
+        assert_eq!(
+            generate_table(&rows, TableConfig::new(config)),
+            "+----+---+\n| \x1b[31mab\x1b[0m | x |\n+----+---+"
+        );
+    }
+
+    #[test]
+    fn test_fit_columns() {
+        let rows = rows(&[&["abcdef", "12"]]);
+        let config = TextBoxConfig::ascii().align_numbers();
+        let table = |config| generate_table(&rows, TableConfig::new(config));
+
+        assert_eq!(
+            table(config.with_width(16)),
+            "+--------+-----+\n| abcdef |  12 |\n+--------+-----+"
+        );
+        assert_eq!(
+            table(config.with_width(11)),
+            "+----+----+\n| ab | 12 |\n+----+----+"
+        );
+        assert_eq!(table(config.with_max_width(40)), table(config));
+        assert_eq!(
+            table(config.with_caption("a long caption")),
+            "+--------+-------+\n| abcdef |    12 |\n<a long caption>-+"
+        );
+        assert_eq!(
+            table(config.with_caption("c").mirrored()),
+            "+--------+----+\n| abcdef | 12 |\n+--------+--<c>"
+        );
+    }
+}
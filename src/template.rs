@@ -0,0 +1,118 @@
+//! Reusable box layouts with placeholders filled at render time.
+use std::{borrow::Borrow, collections::HashMap, fmt::Display, hash::BuildHasher, hash::Hash};
+
+use crate::{Renderer, TextBoxConfig};
+
+/// A box layout whose lines and caption may contain `{key}` placeholders, filled with new values on every render.
+/// The box is measured after the substitution. Use `{{` and `}}` for literal braces;
+/// the placeholders without a value are displayed as is.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// # use std::collections::HashMap;
+/// let template = BoxTemplate::new(
+///     vec![String::from("CPU:    {cpu}%"), String::from("Memory: {mem} MiB")],
+///     TextBoxConfig::default().with_caption("{host}"),
+/// );
+///
+/// let mut values = HashMap::new();
+/// values.insert("cpu", "12");
+/// values.insert("mem", "1024");
+/// values.insert("host", "db-1");
+///
+/// assert_eq!(template.render(&values), "\
+/// ╭──────────────────╮
+/// │ CPU:    12%      │
+/// │ Memory: 1024 MiB │
+/// <db-1>─────────────╯");
+/// ```
+#[derive(Debug, Clone)]
+pub struct BoxTemplate<'a> {
+    lines: Vec<String>,
+    config: TextBoxConfig<'a>,
+}
+
+impl<'a> BoxTemplate<'a> {
+    /// Create a template rendering the given lines with the given config.
+    pub fn new(lines: Vec<String>, config: TextBoxConfig<'a>) -> Self {
+        Self { lines, config }
+    }
+
+    /// The lines of the template, with the placeholders.
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    /// Fill the placeholders with the given values and render the box.
+    pub fn render<K, V, S>(&self, values: &HashMap<K, V, S>) -> String
+    where
+        K: Borrow<str> + Hash + Eq,
+        V: Display,
+        S: BuildHasher,
+    {
+        let value = |key: &str| values.get(key).map(|value| value.to_string());
+        let lines = self
+            .lines
+            .iter()
+            .map(|line| fill(line, value))
+            .collect::<Vec<_>>();
+        let caption = self
+            .config
+            .last_line_caption
+            .map(|caption| fill(caption, value));
+
+        Renderer::new(TextBoxConfig {
+            last_line_caption: caption.as_deref(),
+            ..self.config
+        })
+        .render(&lines)
+    }
+}
+
+/// Replace the `{key}` placeholders of the line with the values returned by `value`.
+pub(crate) fn fill(line: &str, value: impl Fn(&str) -> Option<String>) -> String {
+    let mut result = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(start) = rest.find(['{', '}']) {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            result.push_str(&rest[..1]);
+            rest = &rest[2..];
+            continue;
+        }
+
+        let placeholder = match rest.find('}') {
+            Some(end) if rest.starts_with('{') => &rest[..=end],
+            _ => &rest[..1],
+        };
+        let key = placeholder.trim_start_matches('{').trim_end_matches('}');
+        match value(key).filter(|_| placeholder.len() > 1) {
+            Some(value) => result.push_str(&value),
+            None => result.push_str(placeholder),
+        }
+        rest = &rest[placeholder.len()..];
+    }
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fill() {
+        let value = |key: &str| match key {
+            "a" => Some("1".to_string()),
+            "" => Some("empty".to_string()),
+            _ => None,
+        };
+
+        assert_eq!(fill("no placeholders", value), "no placeholders");
+        assert_eq!(fill("{a}+{a}={b}", value), "1+1={b}");
+        assert_eq!(fill("{{a}} {{{a}}} {}", value), "{a} {1} empty");
+        assert_eq!(fill("} { {a", value), "} { {a");
+    }
+}
@@ -0,0 +1,122 @@
+//! The [`ratatui`] widget, enabled by the `ratatui` feature.
+use ratatui::{buffer::Buffer, layout::Rect, style::Modifier, widgets::Widget};
+
+use crate::{Color, Renderer, Style, StyledLine, TextBoxConfig};
+
+/// A message box rendered into a ratatui [`Buffer`]. The box fits its content,
+/// but is never wider than the area, and the rows below the area are clipped.
+///
+/// The styles of the content, including the ones applied by the
+/// [`line_style`](TextBoxConfig::line_style) hook, are mapped to the ratatui styles.
+///
+/// ```
+/// # use plain_msgbox::*;
+/// use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+///
+/// let msgbox = MsgBoxWidget::new(
+///     vec![StyledLine::new().with_span("ok", Style::new().fg(Color::Green))],
+///     TextBoxConfig::default(),
+/// );
+/// let mut buffer = Buffer::empty(Rect::new(0, 0, 8, 3));
+/// msgbox.render(buffer.area, &mut buffer);
+///
+/// let mut expected = Buffer::with_lines(["╭────╮  ", "│ ok │  ", "╰────╯  "]);
+/// expected.set_style(Rect::new(2, 1, 2, 1), ratatui::style::Color::Green);
+/// assert_eq!(buffer, expected);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MsgBoxWidget<'a> {
+    lines: Vec<StyledLine<'a>>,
+    config: TextBoxConfig<'a>,
+}
+
+impl<'a> MsgBoxWidget<'a> {
+    /// Create a widget displaying the given lines with the given config.
+    pub fn new(lines: Vec<StyledLine<'a>>, config: TextBoxConfig<'a>) -> Self {
+        Self { lines, config }
+    }
+}
+
+impl Widget for &MsgBoxWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let renderer = Renderer::new(self.config);
+        let width = renderer.measure_styled(&self.lines);
+        let msgbox = if width > area.width as usize {
+            Renderer::with_width(self.config, area.width as usize).render_styled(&self.lines)
+        } else {
+            renderer.render_styled(&self.lines)
+        };
+
+        for (y, row) in (area.top()..area.bottom()).zip(msgbox.lines()) {
+            let mut x = area.left();
+            for span in StyledLine::from_ansi(row).spans {
+                let max_width = area.right().saturating_sub(x) as usize;
+                x = buf.set_stringn(x, y, span.text, max_width, span.style).0;
+            }
+        }
+    }
+}
+
+impl Widget for MsgBoxWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        (&self).render(area, buf)
+    }
+}
+
+impl From<Color> for ratatui::style::Color {
+    fn from(color: Color) -> Self {
+        use ratatui::style::Color as C;
+        match color {
+            Color::Black => C::Black,
+            Color::Red => C::Red,
+            Color::Green => C::Green,
+            Color::Yellow => C::Yellow,
+            Color::Blue => C::Blue,
+            Color::Magenta => C::Magenta,
+            Color::Cyan => C::Cyan,
+            // The basic white is the light gray of ratatui.
+            Color::White => C::Gray,
+            Color::Fixed(n) => C::Indexed(n),
+            Color::Rgb(r, g, b) => C::Rgb(r, g, b),
+        }
+    }
+}
+
+impl From<Style> for ratatui::style::Style {
+    fn from(style: Style) -> Self {
+        let mut result = ratatui::style::Style::new();
+        if let Some(fg) = style.fg {
+            result = result.fg(fg.into());
+        }
+        if let Some(bg) = style.bg {
+            result = result.bg(bg.into());
+        }
+        let modifiers = [
+            (style.bold, Modifier::BOLD),
+            (style.dim, Modifier::DIM),
+            (style.italic, Modifier::ITALIC),
+            (style.underline, Modifier::UNDERLINED),
+        ];
+        for (on, modifier) in modifiers.iter() {
+            if *on {
+                result = result.add_modifier(*modifier);
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clipped_render() {
+        let config = TextBoxConfig::default().with_caption("C");
+        let widget = MsgBoxWidget::new(vec!["abcdef".into(), "g".into()], config);
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 6, 3));
+        widget.render(buffer.area, &mut buffer);
+
+        assert_eq!(buffer, Buffer::with_lines(["╭────╮", "│ ab │", "│ g  │"]));
+    }
+}
@@ -0,0 +1,97 @@
+//! JavaScript bindings built with `wasm-bindgen`, enabled by the `wasm` feature.
+use wasm_bindgen::prelude::*;
+
+use crate::{Renderer, TextBoxConfig};
+
+/// The options of the boxes generated from JavaScript.
+///
+/// ```js
+/// const options = new BoxOptions();
+/// options.caption = "Config";
+/// options.dos = true;
+/// console.log(generateBox("a\nb", options));
+/// ```
+#[wasm_bindgen]
+#[derive(Debug, Clone, Default)]
+pub struct BoxOptions {
+    caption: Option<String>,
+    /// Use the DOS box drawing characters.
+    pub dos: bool,
+    /// Mirror the layout for the right-to-left output.
+    pub mirrored: bool,
+    /// Omit the right border.
+    pub ragged: bool,
+    /// The number of spaces before every row.
+    pub indent: usize,
+    /// The fixed width of the box, borders included, or zero to fit the content.
+    pub width: usize,
+}
+
+#[wasm_bindgen]
+impl BoxOptions {
+    /// Create the options of a plain box with the default style.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The caption attached to the bottom border.
+    #[wasm_bindgen(getter)]
+    pub fn caption(&self) -> Option<String> {
+        self.caption.clone()
+    }
+
+    /// Set the caption attached to the bottom border.
+    #[wasm_bindgen(setter)]
+    pub fn set_caption(&mut self, caption: Option<String>) {
+        self.caption = caption;
+    }
+}
+
+impl BoxOptions {
+    /// The config described by these options.
+    fn config(&self) -> TextBoxConfig<'_> {
+        let mut config = if self.dos {
+            TextBoxConfig::dos()
+        } else {
+            TextBoxConfig::default()
+        };
+        config.last_line_caption = self.caption.as_deref();
+        config.mirrored = self.mirrored;
+        config.ragged = self.ragged;
+        config.indent = self.indent;
+        config
+    }
+}
+
+/// Generate a message box displaying every line of the given text.
+#[wasm_bindgen(js_name = generateBox)]
+pub fn generate_box(text: &str, options: Option<BoxOptions>) -> String {
+    let options = options.unwrap_or_default();
+    let lines = text.lines().map(String::from).collect::<Vec<_>>();
+    let config = options.config();
+    let renderer = match options.width {
+        0 => Renderer::new(config),
+        width => Renderer::with_width(config, width),
+    };
+    renderer.render(&lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_box() {
+        assert_eq!(
+            generate_box("a\nbc", None),
+            crate::generate_box(&["a".into(), "bc".into()])
+        );
+
+        let mut options = BoxOptions::new();
+        options.set_caption(Some("C".to_string()));
+        options.dos = true;
+        options.width = 6;
+        assert_eq!(generate_box("abc", Some(options)), "╔════╗\n║ ab ║\n<C>══╝");
+    }
+}
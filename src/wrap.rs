@@ -0,0 +1,96 @@
+//! Breaking the long lines at the word boundaries.
+use std::ops::Range;
+
+use crate::{grapheme, measure::Measure};
+
+/// Break the text into the byte ranges of the lines that are at most `width` columns wide,
+/// preferably at the whitespace. The whitespace at the breaks is dropped, and the words
+/// longer than the width are broken wherever they reach it, but never within a grapheme cluster.
+/// Every line has at least one cluster.
+pub(crate) fn wrap(text: &str, width: usize, measure: Measure<'_>) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    loop {
+        let rest = &text[start..];
+        if measure.width(rest) <= width {
+            ranges.push(start..text.len());
+            return ranges;
+        }
+
+        let fit = measure.truncate(rest, width);
+        if !fit.is_empty() && fit.trim().is_empty() {
+            start += rest.len() - rest.trim_start().len();
+            continue;
+        }
+        let mut end = if rest[fit.len()..].starts_with(char::is_whitespace) {
+            fit.len()
+        } else {
+            fit.trim_end_matches(|c: char| !c.is_whitespace()).len()
+        };
+        if rest[..end].trim().is_empty() {
+            end = fit.len();
+        }
+        if end == 0 {
+            end = grapheme::first_len(rest);
+        }
+
+        ranges.push(start..start + rest[..end].trim_end().len());
+        start += end;
+        start += text[start..].len() - text[start..].trim_start().len();
+        if start >= text.len() {
+            return ranges;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ByteWidth, CharWidth};
+
+    fn pieces(text: &str, width: usize) -> Vec<&str> {
+        wrap(text, width, &ByteWidth)
+            .into_iter()
+            .map(|range| &text[range])
+            .collect()
+    }
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(pieces("", 5), [""]);
+        assert_eq!(pieces("the quick brown fox", 9), ["the quick", "brown fox"]);
+        assert_eq!(
+            pieces("the quick brown fox", 10),
+            ["the quick", "brown fox"]
+        );
+        assert_eq!(pieces("the quick  ", 3), ["the", "qui", "ck"]);
+        assert_eq!(pieces("  indented text", 10), ["  indented", "text"]);
+        assert_eq!(pieces("abc", 0), ["a", "b", "c"]);
+        assert_eq!(pieces("    ab", 2), ["ab"]);
+        assert_eq!(pieces("     ", 2), [""]);
+
+        let text = "héllo wörld";
+        let pieces = wrap(text, 6, &CharWidth)
+            .into_iter()
+            .map(|range| &text[range])
+            .collect::<Vec<_>>();
+        assert_eq!(pieces, ["héllo", "wörld"]);
+    }
+
+    #[test]
+    fn test_wrap_keeps_clusters() {
+        let text = "e\u{301}e\u{301}e\u{301}";
+        let lines = wrap(text, 1, &CharWidth)
+            .into_iter()
+            .map(|range| &text[range])
+            .collect::<Vec<_>>();
+        assert_eq!(lines, ["e\u{301}"; 3]);
+
+        let text = "🇫🇷🇩🇪";
+        let lines = wrap(text, 3, &ByteWidth)
+            .into_iter()
+            .map(|range| &text[range])
+            .collect::<Vec<_>>();
+        assert_eq!(lines, ["🇫🇷", "🇩🇪"]);
+    }
+}